@@ -0,0 +1,109 @@
+use std::ops::DerefMut;
+
+use crate::{Entry, Pool, PullError, StaticEntry, StaticPool};
+
+/// Common pull surface of pools, to write code generic over the pool or to
+/// substitute a mock in tests.
+///
+/// Items are returned to the pool when their entry is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Pool, PoolApi, PullError};
+///
+/// fn handle<P: PoolApi<Vec<u8>>>(pool: &P) -> Result<usize, PullError> {
+///     let mut buf = pool.pull()?;
+///     buf.extend_from_slice(b"hello");
+///     Ok(buf.len())
+/// }
+///
+/// /// A pool which is always exhausted.
+/// struct Exhausted;
+///
+/// impl PoolApi<Vec<u8>> for Exhausted {
+///     type Entry<'a> = Box<Vec<u8>>;
+///
+///     fn pull(&self) -> Result<Self::Entry<'_>, PullError> {
+///         Err(PullError::Exhausted)
+///     }
+///
+///     fn capacity(&self) -> usize {
+///         0
+///     }
+///
+///     fn in_use(&self) -> usize {
+///         0
+///     }
+/// }
+///
+/// let pool: Pool<Vec<u8>> = Pool::with_capacity(1);
+/// assert_eq!(handle(&pool), Ok(5));
+/// assert_eq!(handle(&Exhausted), Err(PullError::Exhausted));
+/// ```
+pub trait PoolApi<T> {
+    /// Entry holding a pulled item.
+    type Entry<'a>: DerefMut<Target = T>
+    where
+        Self: 'a;
+
+    /// Pull an item from the pool.
+    fn pull(&self) -> Result<Self::Entry<'_>, PullError>;
+
+    /// Get the maximum number of items of the pool.
+    fn capacity(&self) -> usize;
+
+    /// Get the number of items currently in use.
+    fn in_use(&self) -> usize;
+
+    /// Get the number of items which can be pulled.
+    fn available(&self) -> usize {
+        self.capacity().saturating_sub(self.in_use())
+    }
+}
+
+impl<T> PoolApi<T> for Pool<T> {
+    type Entry<'a>
+        = Entry<'a, T>
+    where
+        T: 'a;
+
+    fn pull(&self) -> Result<Self::Entry<'_>, PullError> {
+        Pool::pull(self)
+    }
+
+    fn capacity(&self) -> usize {
+        Pool::capacity(self)
+    }
+
+    fn in_use(&self) -> usize {
+        Pool::in_use(self)
+    }
+
+    fn available(&self) -> usize {
+        Pool::available(self)
+    }
+}
+
+impl<T, const N: usize> PoolApi<T> for StaticPool<T, N> {
+    type Entry<'a>
+        = StaticEntry<'a, T, N>
+    where
+        T: 'a;
+
+    fn pull(&self) -> Result<Self::Entry<'_>, PullError> {
+        StaticPool::pull(self)
+    }
+
+    fn capacity(&self) -> usize {
+        StaticPool::capacity(self)
+    }
+
+    fn in_use(&self) -> usize {
+        StaticPool::in_use(self)
+    }
+
+    fn available(&self) -> usize {
+        StaticPool::available(self)
+    }
+}
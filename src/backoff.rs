@@ -0,0 +1,66 @@
+use std::hint::spin_loop;
+use std::thread::yield_now;
+
+/// Number of exponential spinning steps.
+const SPIN_LIMIT: u32 = 6;
+/// Number of total steps before giving up.
+const YIELD_LIMIT: u32 = 10;
+
+/// Strategy to back off when the inner queue of the pool appears empty under contention.
+///
+/// Backing off gives concurrent recycles a chance to land before a pull gives up
+/// or allocates a new item.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{BackoffStrategy, Builder};
+///
+/// let pool = Builder::<u32>::new()
+///     .capacity(2)
+///     .backoff(BackoffStrategy::SpinThenYield)
+///     .build();
+/// assert!(pool.pull().is_ok());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum BackoffStrategy {
+    /// Do not retry, give up or allocate immediately.
+    #[default]
+    None,
+    /// Retry with exponential spinning.
+    Spin,
+    /// Retry with exponential spinning, then yield the current thread.
+    SpinThenYield,
+}
+
+impl BackoffStrategy {
+    /// Back off for the given step. Return `false` if the caller should stop retrying.
+    pub(crate) fn snooze(&self, step: u32) -> bool {
+        match self {
+            BackoffStrategy::None => false,
+            BackoffStrategy::Spin if step < SPIN_LIMIT => {
+                spin(step);
+                true
+            }
+            BackoffStrategy::Spin => false,
+            BackoffStrategy::SpinThenYield if step < SPIN_LIMIT => {
+                spin(step);
+                true
+            }
+            BackoffStrategy::SpinThenYield if step < YIELD_LIMIT => {
+                yield_now();
+                true
+            }
+            BackoffStrategy::SpinThenYield => false,
+        }
+    }
+}
+
+/// Spin `2^step` times.
+fn spin(step: u32) {
+    for _ in 0..1 << step {
+        spin_loop();
+    }
+}
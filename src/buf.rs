@@ -0,0 +1,62 @@
+use bytes::{Buf, BufMut, buf::UninitSlice};
+
+use crate::{Entry, OwnedEntry, UniqueEntry};
+
+/// Reading advances the inner buffer, which panics if the entry is shared.
+impl<'a, T: Buf> Buf for Entry<'a, T> {
+    fn remaining(&self) -> usize {
+        self.get().remaining()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.get().chunk()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        (**self).advance(cnt)
+    }
+}
+
+/// Reading advances the inner buffer, which panics if the entry is shared.
+impl<T: Buf> Buf for OwnedEntry<T> {
+    fn remaining(&self) -> usize {
+        self.get().remaining()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.get().chunk()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        (**self).advance(cnt)
+    }
+}
+
+impl<'a, T: Buf> Buf for UniqueEntry<'a, T> {
+    fn remaining(&self) -> usize {
+        self.get().remaining()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.get().chunk()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.get_mut().advance(cnt)
+    }
+}
+
+// Writes go straight to the inner buffer, which is held exclusively.
+unsafe impl<'a, T: BufMut> BufMut for UniqueEntry<'a, T> {
+    fn remaining_mut(&self) -> usize {
+        self.get().remaining_mut()
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        unsafe { self.get_mut().advance_mut(cnt) }
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        self.get_mut().chunk_mut()
+    }
+}
@@ -1,4 +1,12 @@
-use crate::{Config, Pool};
+use std::error::Error;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::pool::Factory;
+use crate::{
+    BackoffStrategy, ClearPolicy, Config, ConfigError, ExhaustedPolicy, FactoryError, Pool,
+    PoolHandle, PreallocMode, QuarantineWindow, RecycleDecision,
+};
 
 /// A builder for creating a [`Pool`] with custom configuration.
 ///
@@ -11,17 +19,145 @@ use crate::{Config, Pool};
 /// let pool = builder.capacity(10).prealloc(5).build();
 /// assert_eq!(pool.capacity(), 10);
 /// ```
-pub struct Builder<T: Default> {
+pub struct Builder<T> {
     /// Configuration of the pool.
     config: Config<T>,
+    /// Items to start the next built pool with.
+    seed: Vec<T>,
 }
 
 impl<T: Default> Builder<T> {
     /// Create a new builder with default configuration.
     pub fn new() -> Self {
-        Self {
-            config: Config::default(),
-        }
+        Self::from_config(Config::default())
+    }
+}
+
+impl<T: Default> Default for Builder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Builder<T> {
+    fn clone(&self) -> Self {
+        // Seeded items can not be cloned, they only go to a single pool.
+        Self::from_config(self.config.clone())
+    }
+}
+
+impl<T: Default> Builder<T> {
+    /// Create a new builder from a configuration file, in TOML if the file
+    /// has the `toml` extension and in JSON otherwise. See the
+    /// [`Deserialize`](serde::Deserialize) implementation of [`Config`] for the
+    /// supported fields.
+    ///
+    /// Return an error of kind [`InvalidData`](std::io::ErrorKind::InvalidData)
+    /// if the file can not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let path = std::env::temp_dir().join("concurrent-pool-doc.toml");
+    /// std::fs::write(&path, "capacity = 16\nprealloc = 4\n").unwrap();
+    /// let pool = Builder::<Vec<u8>>::from_config_file(&path)
+    ///     .unwrap()
+    ///     .clear_func(Vec::clear)
+    ///     .build();
+    /// assert_eq!(pool.capacity(), 16);
+    /// assert_eq!(pool.available_noalloc(), 4);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    #[cfg(feature = "config-file")]
+    pub fn from_config_file<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let config = match path.extension().is_some_and(|ext| ext == "toml") {
+            true => toml::from_str(&content).map_err(|e| Error::new(ErrorKind::InvalidData, e))?,
+            false => serde_json::from_str(&content)?,
+        };
+        Ok(Self::from_config(config))
+    }
+}
+
+impl<T> Builder<T> {
+    /// Create a new builder with default configuration, creating new items with
+    /// `factory`, for items which do not implement [`Default`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// struct Connection {
+    ///     id: usize,
+    /// }
+    ///
+    /// let pool = Builder::from_factory(|| Connection { id: 7 }).capacity(2).build();
+    /// assert_eq!(pool.pull().unwrap().id, 7);
+    /// ```
+    pub fn from_factory<F>(factory: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Self::from_config(Config::with_factory(factory))
+    }
+
+    /// Set the function to create new items, both preallocated and allocated
+    /// on demand, instead of `T::default()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::new()
+    ///     .capacity(1)
+    ///     .factory(|| String::with_capacity(128))
+    ///     .build();
+    /// assert!(pool.pull().unwrap().capacity() >= 128);
+    /// ```
+    pub fn factory<F>(&mut self, factory: F) -> &mut Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.config.factory = Factory::Func(Arc::new(factory));
+        self
+    }
+
+    /// Set a fallible function to create new items, e.g. to open connections.
+    ///
+    /// When the function fails while allocating an item on demand, the pull
+    /// returns [`PullError::Factory`](crate::PullError::Factory) holding the
+    /// error, and the slot is given back to the pool. Preallocation stops at the
+    /// first failure, leaving the missing items to be allocated on demand.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Builder, PullError};
+    /// use std::io;
+    ///
+    /// let pool = Builder::<Vec<u8>>::new()
+    ///     .capacity(1)
+    ///     .try_factory(|| Err(io::Error::other("out of memory")))
+    ///     .build();
+    /// let Err(PullError::Factory(err)) = pool.pull() else { panic!() };
+    /// assert_eq!(err.downcast_ref::<io::Error>().unwrap().to_string(), "out of memory");
+    /// assert_eq!(pool.allocated(), 0);
+    /// ```
+    pub fn try_factory<F, E>(&mut self, factory: F) -> &mut Self
+    where
+        F: Fn() -> Result<T, E> + Send + Sync + 'static,
+        E: Error + Send + Sync + 'static,
+    {
+        self.config.factory =
+            Factory::TryFunc(Arc::new(move || factory().map_err(FactoryError::new)));
+        self
     }
 
     /// Set the number of preallocated items in the pool.
@@ -30,6 +166,88 @@ impl<T: Default> Builder<T> {
         self
     }
 
+    /// Start the pool with the given items idle, e.g. buffers restored from a
+    /// snapshot, instead of creating them with the factory. Seeded items count
+    /// as preallocated, so the pool preallocates at least as many items.
+    ///
+    /// Seeded items can not be cloned, so they only go to the next pool built
+    /// by this builder and are not kept by clones of the builder.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.capacity(4).seed_items(vec![String::from("warm")]);
+    /// let pool = builder.build();
+    /// assert_eq!(&*pool.pull().unwrap(), "warm");
+    /// assert_eq!(builder.build().available_noalloc(), 0);
+    /// ```
+    pub fn seed_items(&mut self, items: Vec<T>) -> &mut Self {
+        self.seed = items;
+        self
+    }
+
+    /// Set how the preallocated items are created, all at once by default.
+    pub fn prealloc_mode(&mut self, mode: PreallocMode) -> &mut Self {
+        self.config.prealloc_mode = mode;
+        self
+    }
+
+    /// Set a soft capacity, letting the pool grow up to `soft_capacity *
+    /// growth_factor` items to absorb bursts. Items recycled while more than
+    /// `soft_capacity` items are allocated are freed right away.
+    ///
+    /// This overrides the capacity set with [`Builder::capacity`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<u32>::new().soft_capacity(2, 2.0).build();
+    /// assert_eq!(pool.capacity(), 4);
+    /// let items = pool.pull_n(4).unwrap();
+    /// assert!(pool.pull().is_err());
+    /// drop(items);
+    /// assert_eq!(pool.allocated(), 2);
+    /// ```
+    pub fn soft_capacity(&mut self, soft_capacity: usize, growth_factor: f64) -> &mut Self {
+        self.config.soft_capacity = soft_capacity;
+        self.config.capacity = (soft_capacity as f64 * growth_factor.max(1.0)).ceil() as usize;
+        self
+    }
+
+    /// Set the name of the pool, shown in its `Debug` output and panic messages.
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.config.name = Some(name.to_owned());
+        self
+    }
+
+    /// Set what a pull does when the capacity is reached, see [`ExhaustedPolicy`].
+    pub fn on_exhausted(&mut self, policy: ExhaustedPolicy) -> &mut Self {
+        self.config.on_exhausted = policy;
+        self
+    }
+
+    /// Set the minimum number of idle items, replenished right away whenever a
+    /// pull leaves fewer idle items in the pool.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<u32>::new().capacity(10).min_idle(2).build();
+    /// let item = pool.pull().unwrap();
+    /// assert_eq!(pool.available_noalloc(), 2);
+    /// ```
+    pub fn min_idle(&mut self, min_idle: usize) -> &mut Self {
+        self.config.min_idle = min_idle;
+        self
+    }
+
     /// Set the maximum capacity of the pool.
     pub fn capacity(&mut self, capacity: usize) -> &mut Self {
         self.config.capacity = capacity;
@@ -42,6 +260,127 @@ impl<T: Default> Builder<T> {
         self
     }
 
+    /// Set a closure to clear an item before it is returned to the pool, which
+    /// can capture state. Applied after the function set with
+    /// [`clear_func`](Builder::clear_func) if both are set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let max_capacity = 64;
+    /// let pool = Builder::<Vec<u8>>::new()
+    ///     .capacity(1)
+    ///     .clear_with(move |v| {
+    ///         v.clear();
+    ///         v.shrink_to(max_capacity);
+    ///     })
+    ///     .build();
+    /// drop(pool.pull_with(|v| v.reserve(1024)).unwrap());
+    /// assert!(pool.pull().unwrap().capacity() <= max_capacity);
+    /// ```
+    pub fn clear_with<F>(&mut self, func: F) -> &mut Self
+    where
+        F: Fn(&mut T) + Send + Sync + 'static,
+    {
+        self.config.clear_with = Some(Arc::new(func));
+        self
+    }
+
+    /// Set when the clear function is applied, on recycle by default.
+    pub fn clear_policy(&mut self, policy: ClearPolicy) -> &mut Self {
+        self.config.clear_policy = policy;
+        self
+    }
+
+    /// Set the function to apply to an item each time it is pulled, e.g. to
+    /// stamp per-pull state, while expensive cleanup stays in the clear
+    /// function applied on recycle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<u32>::new().capacity(1).before_pull(|n| *n += 1).build();
+    /// drop(pool.pull().unwrap());
+    /// assert_eq!(*pool.pull().unwrap(), 2);
+    /// ```
+    pub fn before_pull(&mut self, func: fn(&mut T)) -> &mut Self {
+        self.config.before_pull = Some(func);
+        self
+    }
+
+    /// Hold recycled items back for `window` before they can be reused, to
+    /// debug code using items after returning them. See [`QuarantineWindow`].
+    pub fn quarantine(&mut self, window: QuarantineWindow) -> &mut Self {
+        self.config.quarantine = Some(window);
+        self
+    }
+
+    /// Set whether a panic of the clear function or of the recycle hook is
+    /// resumed, `true` by default. The item is destroyed and its slot freed
+    /// either way.
+    pub fn propagate_clear_panics(&mut self, propagate: bool) -> &mut Self {
+        self.config.propagate_clear_panics = propagate;
+        self
+    }
+
+    /// Set the function applied to an item when it is recycled, after the
+    /// clear function. It can reset the item, and decide whether it is reused,
+    /// destroyed or set aside, see [`RecycleDecision`].
+    pub fn on_recycle(&mut self, func: fn(&mut T) -> RecycleDecision) -> &mut Self {
+        self.config.on_recycle = Some(func);
+        self
+    }
+
+    /// Set the function called with the pool name and the deadline of a lease
+    /// when it is revoked, see [`Pool::pull_leased`].
+    pub fn on_lease_revoked(&mut self, func: fn(&str, Instant)) -> &mut Self {
+        self.config.on_lease_revoked = Some(func);
+        self
+    }
+
+    /// Set the function reporting whether the process is under memory
+    /// pressure. Idle items are freed with [`Pool::reclaim_on_pressure`] when
+    /// it returns `true` during [`Pool::maintain`].
+    ///
+    /// With the `memory-pressure` feature,
+    /// [`cgroup_memory_pressure`](crate::pressure::cgroup_memory_pressure) reads
+    /// the pressure of the cgroup of the process on Linux.
+    pub fn memory_pressure(&mut self, func: fn() -> bool) -> &mut Self {
+        self.config.memory_pressure = Some(func);
+        self
+    }
+
+    /// Report the items held for longer than `threshold` to `func`, called by
+    /// [`Pool::maintain`] with the pool name and where the item was pulled,
+    /// once per pull.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<u32>::new()
+    ///     .capacity(2)
+    ///     .max_hold_warning(Duration::from_secs(30), |pool, record| {
+    ///         eprintln!("item of `{pool}` held since {}", record.location());
+    ///     })
+    ///     .spawn_maintenance(Duration::from_secs(1));
+    /// ```
+    #[cfg(feature = "instrument")]
+    pub fn max_hold_warning(
+        &mut self,
+        threshold: Duration,
+        func: crate::HoldWarningFn,
+    ) -> &mut Self {
+        self.config.max_hold_warning = Some((threshold, func));
+        self
+    }
+
     /// Enable or disable auto reclaiming allocated items and free them to reduce memory usage.
     pub fn auto_reclaim(&mut self, enable: bool) -> &mut Self {
         self.config.auto_reclaim = enable;
@@ -60,15 +399,176 @@ impl<T: Default> Builder<T> {
         self
     }
 
+    /// Free idle items beyond `prealloc` once they have been idle for longer
+    /// than `timeout`.
+    pub fn idle_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.config.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Destroy items older than `lifetime` instead of reusing them. New items
+    /// are created on demand to replace them.
+    pub fn max_lifetime(&mut self, lifetime: Duration) -> &mut Self {
+        self.config.max_lifetime = Some(lifetime);
+        self
+    }
+
+    /// Destroy items once they have been pulled `uses` times instead of
+    /// reusing them. New items are created on demand to replace them.
+    pub fn max_uses(&mut self, uses: usize) -> &mut Self {
+        self.config.max_uses = uses;
+        self
+    }
+
+    /// Reclaim idle items with hysteresis when `auto_reclaim` is enabled: once
+    /// more than `high` items are idle, free them down to `low`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<u32>::new()
+    ///     .capacity(10)
+    ///     .enable_auto_reclaim()
+    ///     .idle_watermarks(2, 4)
+    ///     .build();
+    /// let items = pool.pull_n(10).unwrap();
+    /// drop(items);
+    /// // The fifth idle item freed three of them, and so did the eighth.
+    /// assert_eq!(pool.allocated(), 4);
+    /// ```
+    pub fn idle_watermarks(&mut self, low: usize, high: usize) -> &mut Self {
+        self.config.idle_low_watermark = low;
+        self.config.idle_high_watermark = high;
+        self
+    }
+
+    /// Set the maximum number of idle items freed each time reclamation is
+    /// triggered, 1 by default.
+    pub fn reclaim_batch(&mut self, batch: usize) -> &mut Self {
+        self.config.reclaim_batch = batch;
+        self
+    }
+
     /// Set the threshold for idle items to judge as a `surplus-pull` when `auto_reclaim` is enabled.
     pub fn idle_threshold_for_surpluspull(&mut self, threshold: usize) -> &mut Self {
         self.config.idle_threshold_for_surpluspull = threshold;
         self
     }
 
-    /// Build the pool with the current configuration.
+    /// Set the strategy to back off when the pool appears empty under contention.
+    pub fn backoff(&mut self, strategy: BackoffStrategy) -> &mut Self {
+        self.config.backoff = strategy;
+        self
+    }
+
+    /// Attach metadata of type `M` to each item of the pool, created with
+    /// `M::default()` along with the item. See [`Entry::metadata`](crate::Entry::metadata).
+    pub fn with_metadata<M>(&mut self) -> &mut Self
+    where
+        M: Default + Send + Sync + 'static,
+    {
+        self.config.metadata = Some(|| Box::new(M::default()));
+        self
+    }
+
+    /// Create a new builder starting from the given configuration.
+    pub fn from_config(config: Config<T>) -> Self {
+        Self {
+            config,
+            seed: Vec::new(),
+        }
+    }
+
+    /// Get the current configuration of the builder.
+    pub fn config(&self) -> &Config<T> {
+        &self.config
+    }
+
+    /// Build the pool with the current configuration. The builder keeps its
+    /// configuration, so it can build several similar pools.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let mut builder = Builder::<Vec<u8>>::new();
+    /// builder.capacity(4).clear_func(Vec::clear);
+    /// let shards: Vec<_> = (0..8).map(|_| builder.build()).collect();
+    /// assert!(shards.iter().all(|pool| pool.capacity() == 4));
+    /// ```
     pub fn build(&mut self) -> Pool<T> {
-        let config = std::mem::take(&mut self.config);
-        Pool::with_config(config)
+        Pool::with_config_and_seed(self.config.clone(), std::mem::take(&mut self.seed))
+    }
+
+    /// Build the pool with the current configuration, returning an error
+    /// instead of panicking if it is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Builder, ConfigError};
+    ///
+    /// let err = Builder::<u32>::new().capacity(0).prealloc(1).try_build().unwrap_err();
+    /// assert_eq!(err, ConfigError::ZeroCapacityWithPrealloc { prealloc: 1 });
+    /// assert!(Builder::<u32>::new().capacity(2).try_build().is_ok());
+    /// ```
+    pub fn try_build(&mut self) -> Result<Pool<T>, ConfigError> {
+        self.config.validate_with_seed(self.seed.len())?;
+        Ok(self.build())
+    }
+
+    /// Build a shared pool with the current configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<usize>::new().capacity(10).build_shared();
+    /// let item = pool.pull_owned().unwrap();
+    /// assert_eq!(pool.in_use(), 1);
+    /// ```
+    pub fn build_shared(&mut self) -> PoolHandle<T> {
+        PoolHandle::from(self.build())
+    }
+
+    /// Build a shared pool with the current configuration, and spawn a thread
+    /// running [`Pool::maintain`] on it every `interval`, so idle timeouts,
+    /// reclamation and `min_idle` apply even while the pool is quiet.
+    ///
+    /// The thread stops once the pool is closed or dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<usize>::new()
+    ///     .capacity(4)
+    ///     .min_idle(2)
+    ///     .spawn_maintenance(Duration::from_millis(1));
+    /// std::thread::sleep(Duration::from_millis(50));
+    /// assert_eq!(pool.allocated(), 2);
+    /// ```
+    pub fn spawn_maintenance(&mut self, interval: Duration) -> PoolHandle<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        let pool = self.build_shared();
+        let weak = Arc::downgrade(pool.as_arc());
+        std::thread::spawn(move || {
+            loop {
+                std::thread::sleep(interval);
+                match weak.upgrade() {
+                    Some(pool) if !pool.is_closed() => pool.maintain(),
+                    _ => break,
+                }
+            }
+        });
+        pool
     }
 }
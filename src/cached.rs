@@ -0,0 +1,170 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use crate::{OwnedEntry, Pool, PullError};
+
+thread_local! {
+    /// Idle items cached by the current thread, keyed by the address of their
+    /// pool. Dropped items go back to their pool when the thread exits.
+    static CACHES: RefCell<HashMap<usize, Box<dyn Any>>> = RefCell::default();
+}
+
+/// A pool keeping a small stack of idle items per thread in front of a shared
+/// [`Pool`], for workloads where items are pulled and recycled on the same
+/// thread.
+///
+/// Items recycled while the cache of the current thread is full go back to the
+/// shared pool, as do the cached items when the thread exits. Cached items are
+/// counted as in use by the shared pool.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Builder, CachedPool};
+///
+/// let pool = Builder::<Vec<u8>>::new()
+///     .capacity(8)
+///     .clear_func(Vec::clear)
+///     .build_shared()
+///     .into_arc();
+/// let cached = CachedPool::new(pool.clone(), 2);
+/// let mut item = cached.pull().unwrap();
+/// item.push(1);
+/// drop(item);
+/// // The item is cached by this thread instead of going back to the pool.
+/// assert_eq!(pool.in_use(), 1);
+/// assert!(cached.pull().unwrap().is_empty());
+/// cached.flush();
+/// assert_eq!(pool.in_use(), 0);
+/// ```
+#[derive(Debug)]
+pub struct CachedPool<T> {
+    pool: Arc<Pool<T>>,
+    limit: usize,
+}
+
+impl<T: 'static> CachedPool<T> {
+    /// Create a cached pool over `pool`, keeping up to `limit` idle items per
+    /// thread.
+    pub fn new(pool: Arc<Pool<T>>, limit: usize) -> Self {
+        Self { pool, limit }
+    }
+
+    /// Get the shared pool.
+    pub fn pool(&self) -> &Arc<Pool<T>> {
+        &self.pool
+    }
+
+    /// Pull an item from the cache of the current thread, or from the shared
+    /// pool if the cache is empty. Never block, even with
+    /// [`ExhaustedPolicy::Block`](crate::ExhaustedPolicy::Block), since the
+    /// missing items may sit in the thread caches.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull(&self) -> Result<CachedEntry<T>, PullError> {
+        if self.pool.is_closed() {
+            return Err(PullError::Closed);
+        }
+        let entry = loop {
+            match with_cache(&self.pool, Vec::pop).flatten() {
+                // Cached items may outlive `max_lifetime`, and are then
+                // destroyed by the shared pool.
+                Some(entry) if self.pool.is_worn_out(entry.item.as_ref().unwrap()) => drop(entry),
+                Some(mut entry) => {
+                    let item = entry.item.as_mut().unwrap();
+                    item.checkout();
+                    self.pool.before_pull(item, true);
+                    break entry;
+                }
+                None => break self.pool.try_pull_owned()?,
+            }
+        };
+        Ok(CachedEntry {
+            entry: Some(entry),
+            limit: self.limit,
+        })
+    }
+
+    /// Return the items cached by the current thread to the shared pool.
+    pub fn flush(&self) {
+        drop(with_cache(&self.pool, std::mem::take));
+    }
+}
+
+/// Apply `func` to the cache of the current thread for `pool`. Return `None`
+/// if the thread is exiting.
+///
+/// Entries must not be dropped inside `func`, as it holds the cache borrowed.
+fn with_cache<T: 'static, R>(
+    pool: &Arc<Pool<T>>,
+    func: impl FnOnce(&mut Vec<OwnedEntry<T>>) -> R,
+) -> Option<R> {
+    CACHES
+        .try_with(|caches| {
+            let mut caches = caches.borrow_mut();
+            let cache = caches
+                .entry(Arc::as_ptr(pool) as usize)
+                .or_insert_with(|| Box::new(Vec::<OwnedEntry<T>>::new()));
+            // A previous pool at the same address has been dropped, so its
+            // cache is empty.
+            if !cache.is::<Vec<OwnedEntry<T>>>() {
+                *cache = Box::new(Vec::<OwnedEntry<T>>::new());
+            }
+            func(cache.downcast_mut().unwrap())
+        })
+        .ok()
+}
+
+/// An item pulled from a [`CachedPool`], returned to the cache of the current
+/// thread when dropped.
+pub struct CachedEntry<T: 'static> {
+    // `entry` is always `Some` until the entry is dropped.
+    entry: Option<OwnedEntry<T>>,
+    limit: usize,
+}
+
+impl<T: 'static> Drop for CachedEntry<T> {
+    fn drop(&mut self) {
+        let Some(mut entry) = self.entry.take() else {
+            return;
+        };
+        let pool = entry.pool.clone();
+        let fits = || with_cache(&pool, |cache| cache.len() < self.limit) == Some(true);
+        // Worn out items go back to the shared pool, which destroys them.
+        if pool.is_closed() || pool.is_worn_out(entry.item.as_ref().unwrap()) || !fits() {
+            return;
+        }
+        // The clear function may use the cache of another pool, so it is
+        // applied before borrowing the cache again.
+        pool.clear_recycled(entry.item.as_mut().unwrap());
+        with_cache(&pool, |cache| cache.push(entry));
+    }
+}
+
+impl<T: 'static> Deref for CachedEntry<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        self.entry.as_ref().unwrap()
+    }
+}
+
+impl<T: 'static> DerefMut for CachedEntry<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.entry.as_mut().unwrap()
+    }
+}
+
+impl<T: Debug + 'static> Debug for CachedEntry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Display + 'static> Display for CachedEntry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
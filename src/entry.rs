@@ -1,10 +1,17 @@
+use std::any::Any;
+use std::borrow::Borrow;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
+use std::mem::MaybeUninit;
+use std::ops::DerefMut;
 use std::sync::Arc;
 use std::sync::atomic::Ordering::*;
+use std::sync::atomic::{AtomicU8, AtomicU64, fence};
 use std::{ops::Deref, ptr::NonNull, sync::atomic::AtomicUsize};
 
-use crate::Pool;
+use crate::{
+    ErasedEntry, MappedEntry, MappedMutEntry, OwnedMappedEntry, Pool, PoolToken, PullError,
+};
 
 /// An entry in the pool.
 ///
@@ -12,15 +19,29 @@ use crate::Pool;
 /// to the [`Pool`].
 /// When the last `Entry` is dropped, the item is returned to the pool.
 ///
-#[derive(Debug)]
-pub struct Entry<'a, T: Default> {
+/// `Entry` is `Send` when `T` is `Send + Sync`, so it can be moved into threads
+/// spawned by [`std::thread::scope`] without wrapping the pool in an `Arc`.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::Pool;
+///
+/// let pool: Pool<u32> = Pool::with_capacity(2);
+/// std::thread::scope(|s| {
+///     let item = pool.pull_with(|x| *x = 42).unwrap();
+///     s.spawn(move || assert_eq!(*item, 42));
+/// });
+/// assert_eq!(pool.available(), 2);
+/// ```
+pub struct Entry<'a, T> {
     // When the last reference is dropped, the item is returned to the pool.
     // `item` is always `Some` before the last reference is dropped.
     pub(crate) item: Option<Prc<T>>,
     pub(crate) pool: &'a Pool<T>,
 }
 
-impl<'a, T: Default> Clone for Entry<'a, T> {
+impl<'a, T> Clone for Entry<'a, T> {
     /// Makes a clone of the `Entry` that points to the same allocation.
     fn clone(&self) -> Self {
         Self {
@@ -30,51 +51,115 @@ impl<'a, T: Default> Clone for Entry<'a, T> {
     }
 }
 
-impl<'a, T: Default + PartialEq> PartialEq for Entry<'a, T> {
+impl<'a, T: PartialEq> PartialEq for Entry<'a, T> {
     fn eq(&self, other: &Self) -> bool {
         self.item.eq(&other.item)
     }
 }
 
-impl<'a, T: Default + Eq> Eq for Entry<'a, T> {}
+impl<'a, T: Eq> Eq for Entry<'a, T> {}
 
-impl<'a, T: Default + PartialOrd> PartialOrd for Entry<'a, T> {
+impl<'a, T: PartialOrd> PartialOrd for Entry<'a, T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         self.item.partial_cmp(&other.item)
     }
 }
 
-impl<'a, T: Default + Ord> Ord for Entry<'a, T> {
+impl<'a, T: Ord> Ord for Entry<'a, T> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.item.cmp(&other.item)
     }
 }
 
-impl<'a, T: Default + Hash> Hash for Entry<'a, T> {
+impl<'a, T: Hash> Hash for Entry<'a, T> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.item.hash(state)
+        self.get().hash(state)
+    }
+}
+
+impl<'a, T: Debug> Debug for Entry<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<'a, T: Display> Display for Entry<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<'a, T> AsRef<T> for Entry<'a, T> {
+    fn as_ref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<'a, T> Borrow<T> for Entry<'a, T> {
+    fn borrow(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<'a> AsRef<[u8]> for Entry<'a, Vec<u8>> {
+    fn as_ref(&self) -> &[u8] {
+        self.get()
+    }
+}
+
+impl<'a> AsRef<[u8]> for Entry<'a, String> {
+    fn as_ref(&self) -> &[u8] {
+        self.get().as_bytes()
+    }
+}
+
+impl<'a> AsRef<str> for Entry<'a, String> {
+    fn as_ref(&self) -> &str {
+        self.get()
     }
 }
 
-impl<'a, T: Default> Drop for Entry<'a, T> {
+impl<'a, T> Drop for Entry<'a, T> {
     fn drop(&mut self) {
-        if self.item.as_ref().is_some_and(|i| i.dec_ref() == 1) {
-            // This was the last reference, return to the pool.
-            let item = self.item.take().unwrap();
-            self.pool.recycle(item);
+        if let Some(item) = self.item.take() {
+            self.pool.release_ref(item);
         }
     }
 }
 
-impl<'a, T: Default> Deref for Entry<'a, T> {
+impl<'a, T> Deref for Entry<'a, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
         self.item.as_ref().unwrap()
     }
 }
 
+impl<'a, T> DerefMut for Entry<'a, T> {
+    /// Mutably dereference the inner item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other references to the inner item exist. Use
+    /// [`Entry::get_mut`] for a non-panicking variant.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<String> = Pool::with_capacity(1);
+    /// let mut item = pool.pull().unwrap();
+    /// item.push_str("hello");
+    /// assert_eq!(&*item, "hello");
+    /// ```
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.get_mut()
+            .expect("can not mutably dereference a shared entry")
+    }
+}
+
 #[cfg(feature = "serde")]
-impl<'a, T: Default + serde::Serialize> serde::Serialize for Entry<'a, T> {
+impl<'a, T: serde::Serialize> serde::Serialize for Entry<'a, T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -83,10 +168,36 @@ impl<'a, T: Default + serde::Serialize> serde::Serialize for Entry<'a, T> {
     }
 }
 
-impl<'a, T: Default> Entry<'a, T> {
+// The item lives in a heap allocation which is never moved while referenced.
+#[cfg(feature = "stable_deref")]
+unsafe impl<'a, T> stable_deref_trait::StableDeref for Entry<'a, T> {}
+#[cfg(feature = "stable_deref")]
+unsafe impl<'a, T> stable_deref_trait::CloneStableDeref for Entry<'a, T> {}
+
+impl<'a, T> Entry<'a, T> {
     /// Get reference to the inner item.
     pub fn get(&self) -> &T {
-        &self
+        self
+    }
+
+    /// Get the pool this entry belongs to, e.g. to pull sibling items.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Entry, Pool};
+    ///
+    /// fn fork(entry: &Entry<'_, u32>) -> u32 {
+    ///     let sibling = entry.pool().pull_with(|x| *x = **entry + 1).unwrap();
+    ///     *sibling
+    /// }
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(2);
+    /// let item = pool.pull_with(|x| *x = 1).unwrap();
+    /// assert_eq!(fork(&item), 2);
+    /// ```
+    pub fn pool(&self) -> &'a Pool<T> {
+        self.pool
     }
 
     /// Get mutable reference to the inner item if there are no other references.
@@ -97,9 +208,368 @@ impl<'a, T: Default> Entry<'a, T> {
 
     /// Get mutable reference to the inner item without checking for other references.
     ///
+    /// # Safety
+    ///
+    /// The caller must ensure that no other references to the inner item exist
+    /// while the returned mutable reference is alive.
     pub unsafe fn get_mut_unchecked(&mut self) -> &mut T {
         unsafe { Prc::get_mut_unchecked(self.item.as_mut().unwrap()) }
     }
+
+    /// Get the number of references to the inner item, including this one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(1);
+    /// let mut item = pool.pull().unwrap();
+    /// let clone = item.clone();
+    /// assert_eq!(item.strong_count(), 2);
+    /// drop(clone);
+    /// assert_eq!(item.strong_count(), 1);
+    /// assert!(item.get_mut().is_some());
+    /// ```
+    pub fn strong_count(&self) -> usize {
+        self.item.as_ref().unwrap().count()
+    }
+
+    /// Get the number of times the inner item has been pulled from the pool,
+    /// including the current checkout.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(1);
+    /// for _ in 0..3 {
+    ///     pool.pull().unwrap();
+    /// }
+    /// assert_eq!(pool.pull().unwrap().use_count(), 4);
+    /// ```
+    pub fn use_count(&self) -> usize {
+        self.item.as_ref().unwrap().use_count()
+    }
+
+    /// Get the metadata attached to the inner item if the pool was built with
+    /// metadata of type `M`. Otherwise, return `None`.
+    ///
+    /// The metadata belongs to the slot of the item and is kept across
+    /// recycles.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// #[derive(Default)]
+    /// struct Origin {
+    ///     shard: u32,
+    /// }
+    ///
+    /// let pool = Builder::<Vec<u8>>::new().capacity(1).with_metadata::<Origin>().build();
+    /// let mut item = pool.pull().unwrap();
+    /// item.metadata_mut::<Origin>().unwrap().shard = 3;
+    /// drop(item);
+    /// let item = pool.pull().unwrap();
+    /// assert_eq!(item.metadata::<Origin>().unwrap().shard, 3);
+    /// assert!(item.metadata::<u32>().is_none());
+    /// ```
+    pub fn metadata<M: Any>(&self) -> Option<&M> {
+        self.item.as_ref().unwrap().metadata()
+    }
+
+    /// Get mutable reference to the metadata attached to the inner item if it
+    /// is of type `M` and there are no other references. Otherwise, return
+    /// `None`.
+    pub fn metadata_mut<M: Any>(&mut self) -> Option<&mut M> {
+        Prc::metadata_mut(self.item.as_mut().unwrap())
+    }
+
+    /// Get how long the item has been checked out since it was pulled, e.g. to
+    /// log long-held items and spot leaks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::time::Duration;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(1);
+    /// let item = pool.pull().unwrap();
+    /// std::thread::sleep(Duration::from_millis(10));
+    /// assert!(item.held_for() >= Duration::from_millis(10));
+    /// ```
+    #[cfg(feature = "instrument")]
+    pub fn held_for(&self) -> std::time::Duration {
+        self.item.as_ref().unwrap().held_for()
+    }
+
+    /// Get mutable reference to the inner item, cloning it into a fresh item of
+    /// the pool first if other references exist (copy-on-write).
+    ///
+    /// If the pool is exhausted, the clone is an unpooled value which is
+    /// dropped instead of being recycled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<String> = Pool::with_capacity(2);
+    /// let mut item = pool.pull_with(|s| s.push_str("hello")).unwrap();
+    /// let snapshot = item.clone();
+    /// item.make_mut().push_str(" world");
+    /// assert_eq!(&*item, "hello world");
+    /// assert_eq!(&*snapshot, "hello");
+    /// assert_eq!(pool.in_use(), 2);
+    /// ```
+    pub fn make_mut(&mut self) -> &mut T
+    where
+        T: Clone,
+    {
+        if !self.item.as_ref().unwrap().is_unique() {
+            let fresh = self.pool.pull_clone_of(self.get());
+            let old = self.item.replace(fresh).unwrap();
+            self.pool.release_ref(old);
+        }
+        unsafe { self.get_mut_unchecked() }
+    }
+
+    /// Replace the inner item with `value`, returning the old one, e.g. to keep
+    /// the old buffer and leave a fresh one in the pool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other references to the inner item exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(1);
+    /// let mut item = pool.pull_with(|v| v.push(1)).unwrap();
+    /// let old = item.replace(Vec::with_capacity(64));
+    /// assert_eq!(old, vec![1]);
+    /// drop(item);
+    /// assert!(pool.pull().unwrap().capacity() >= 64);
+    /// ```
+    pub fn replace(&mut self, value: T) -> T {
+        std::mem::replace(&mut **self, value)
+    }
+
+    /// Take the inner item out, leaving a default value in the slot so it stays
+    /// usable once recycled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other references to the inner item exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<String> = Pool::with_capacity(1);
+    /// let mut item = pool.pull_with(|s| s.push_str("hello")).unwrap();
+    /// assert_eq!(item.take(), "hello");
+    /// assert_eq!(&*item, "");
+    /// ```
+    pub fn take(&mut self) -> T
+    where
+        T: Default,
+    {
+        std::mem::take(&mut **self)
+    }
+
+    /// Take the inner item out if this is the only reference, leaving a default
+    /// value in the slot which is recycled. Otherwise, return the entry back.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<String> = Pool::with_capacity(1);
+    /// let item = pool.pull_with(|s| s.push_str("hello")).unwrap();
+    /// let clone = item.clone();
+    /// let item = item.try_unwrap().unwrap_err();
+    /// drop(clone);
+    /// assert_eq!(item.try_unwrap().unwrap(), "hello");
+    /// assert_eq!(pool.available(), 1);
+    /// assert_eq!(&*pool.pull().unwrap(), "");
+    /// ```
+    pub fn try_unwrap(mut self) -> Result<T, Self>
+    where
+        T: Default,
+    {
+        match self.get_mut() {
+            Some(item) => Ok(std::mem::take(item)),
+            None => Err(self),
+        }
+    }
+
+    /// Permanently remove the inner item from the pool if this is the only
+    /// reference. Otherwise, return the entry back.
+    ///
+    /// The item no longer counts as allocated, so the pool can allocate a
+    /// replacement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<String> = Pool::with_capacity(1);
+    /// let item = pool.pull_with(|s| s.push_str("cached")).unwrap();
+    /// let value = item.detach().unwrap();
+    /// assert_eq!(value, "cached");
+    /// assert_eq!(pool.allocated(), 0);
+    /// assert_eq!(pool.available(), 1);
+    /// ```
+    pub fn detach(mut self) -> Result<T, Self> {
+        match self.item.as_ref().unwrap().is_unique() {
+            true => Ok(self.pool.detach(self.item.take().unwrap())),
+            false => Err(self),
+        }
+    }
+
+    /// Destroy the inner item instead of returning it to the pool, e.g. when it
+    /// is known to be corrupted.
+    ///
+    /// If other references exist, the item is destroyed when the last one is
+    /// dropped. The item no longer counts as allocated afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(1);
+    /// let item = pool.pull_with(|v| v.resize(1 << 20, 0)).unwrap();
+    /// item.discard();
+    /// assert_eq!(pool.allocated(), 0);
+    /// assert!(pool.pull().unwrap().is_empty());
+    /// ```
+    pub fn discard(self) {
+        self.item.as_ref().unwrap().discard();
+    }
+
+    /// Pull a new item from the pool and clone the inner item into it, e.g. to
+    /// fork a pooled message for fan-out without aliasing. Return
+    /// [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(2);
+    /// let item = pool.pull_with(|v| v.push(1)).unwrap();
+    /// let mut copy = item.duplicate().unwrap();
+    /// copy.push(2);
+    /// assert_eq!(*item, vec![1]);
+    /// assert_eq!(*copy, vec![1, 2]);
+    /// assert!(item.duplicate().is_err());
+    /// ```
+    pub fn duplicate(&self) -> Result<Entry<'a, T>, PullError>
+    where
+        T: Clone,
+    {
+        self.pool.try_pull().map(|mut entry| {
+            self.pool
+                .guarded(&mut entry.item, |item| item.clone_from(self.get()));
+            entry
+        })
+    }
+
+    /// Keep the inner item alive forever and get a `'static` reference to it,
+    /// e.g. for one-time global initializations pulled from a warm pool.
+    ///
+    /// The item is never returned to the pool and no longer counts as
+    /// allocated, so the pool can allocate a replacement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<String> = Pool::with_capacity(1);
+    /// let item = pool.pull_with(|s| s.push_str("config")).unwrap();
+    /// let config: &'static String = item.leak();
+    /// assert_eq!(config, "config");
+    /// assert_eq!(pool.allocated(), 0);
+    /// assert_eq!(pool.available(), 1);
+    /// ```
+    pub fn leak(mut self) -> &'static T
+    where
+        T: 'static,
+    {
+        self.pool.leak(self.item.take().unwrap())
+    }
+
+    /// Create a [`WeakEntry`] to the item which does not keep it checked out.
+    pub fn downgrade(&self) -> WeakEntry<'a, T> {
+        WeakEntry {
+            item: self.item.as_ref().unwrap().downgrade(),
+            pool: self.pool,
+        }
+    }
+
+    /// Convert into an [`OwnedEntry`] holding the given `Arc` reference to the
+    /// pool, e.g. to send the entry across a channel.
+    ///
+    /// `pool` must be the pool this entry was pulled from, which is checked in
+    /// debug builds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_with(|x| *x = 42).unwrap();
+    /// let owned = item.into_owned(&pool);
+    /// std::thread::spawn(move || assert_eq!(*owned, 42))
+    ///     .join()
+    ///     .unwrap();
+    /// assert_eq!(pool.available(), 1);
+    /// ```
+    pub fn into_owned(mut self, pool: &Arc<Pool<T>>) -> OwnedEntry<T> {
+        debug_assert!(
+            std::ptr::eq(self.pool, &**pool),
+            "the entry does not belong to the given pool"
+        );
+        OwnedEntry {
+            item: self.item.take(),
+            pool: pool.clone(),
+        }
+    }
+
+    /// Project the entry into a part of the item, e.g. a field, keeping the
+    /// item checked out until the returned [`MappedEntry`] is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<(u32, String)> = Pool::with_capacity(1);
+    /// let item = pool.pull_with(|t| t.0 = 7).unwrap();
+    /// let id = item.map(|t| &t.0);
+    /// assert_eq!(*id, 7);
+    /// ```
+    pub fn map<U, F>(self, f: F) -> MappedEntry<'a, T, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&T) -> &U,
+    {
+        MappedEntry::new(self, f)
+    }
 }
 
 /// An owned entry in the pool.
@@ -108,14 +578,14 @@ impl<'a, T: Default> Entry<'a, T> {
 /// reference to the [`Pool`].
 /// When the last `OwnedEntry` is dropped, the item is returned to the pool.
 ///
-pub struct OwnedEntry<T: Default> {
+pub struct OwnedEntry<T> {
     // When the last reference is dropped, the item is returned to the pool.
     // `item` is always `Some` before the last reference is dropped.
     pub(crate) item: Option<Prc<T>>,
     pub(crate) pool: Arc<Pool<T>>,
 }
 
-impl<T: Default> Clone for OwnedEntry<T> {
+impl<T> Clone for OwnedEntry<T> {
     /// Makes a clone of the `OwnedEntry` that points to the same allocation.
     fn clone(&self) -> Self {
         Self {
@@ -125,21 +595,21 @@ impl<T: Default> Clone for OwnedEntry<T> {
     }
 }
 
-impl<T: Default + PartialEq> PartialEq for OwnedEntry<T> {
+impl<T: PartialEq> PartialEq for OwnedEntry<T> {
     fn eq(&self, other: &Self) -> bool {
         self.item.eq(&other.item)
     }
 }
 
-impl<T: Default + Eq> Eq for OwnedEntry<T> {}
+impl<T: Eq> Eq for OwnedEntry<T> {}
 
-impl<T: Default + PartialOrd> PartialOrd for OwnedEntry<T> {
+impl<T: PartialOrd> PartialOrd for OwnedEntry<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         self.item.partial_cmp(&other.item)
     }
 }
 
-impl<T: Default + Ord> Ord for OwnedEntry<T> {
+impl<T: Ord> Ord for OwnedEntry<T> {
     /// Comparison for two `OwnedEntry`
     ///
     /// # Example
@@ -158,31 +628,84 @@ impl<T: Default + Ord> Ord for OwnedEntry<T> {
     }
 }
 
-impl<T: Default + Hash> Hash for OwnedEntry<T> {
+impl<T: Hash> Hash for OwnedEntry<T> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.item.hash(state)
+        self.get().hash(state)
+    }
+}
+
+impl<T: Debug> Debug for OwnedEntry<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<T: Display> Display for OwnedEntry<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<T> AsRef<T> for OwnedEntry<T> {
+    fn as_ref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T> Borrow<T> for OwnedEntry<T> {
+    fn borrow(&self) -> &T {
+        self.get()
+    }
+}
+
+impl AsRef<[u8]> for OwnedEntry<Vec<u8>> {
+    fn as_ref(&self) -> &[u8] {
+        self.get()
+    }
+}
+
+impl AsRef<[u8]> for OwnedEntry<String> {
+    fn as_ref(&self) -> &[u8] {
+        self.get().as_bytes()
+    }
+}
+
+impl AsRef<str> for OwnedEntry<String> {
+    fn as_ref(&self) -> &str {
+        self.get()
     }
 }
 
-impl<T: Default> Drop for OwnedEntry<T> {
+impl<T> Drop for OwnedEntry<T> {
     fn drop(&mut self) {
-        if self.item.as_ref().is_some_and(|i| i.dec_ref() == 1) {
-            // This was the last reference, return to the pool.
-            let item = self.item.take().unwrap();
-            self.pool.recycle(item);
+        if let Some(item) = self.item.take() {
+            self.pool.release_ref(item);
         }
     }
 }
 
-impl<T: Default> Deref for OwnedEntry<T> {
+impl<T> Deref for OwnedEntry<T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
         self.item.as_ref().unwrap()
     }
 }
 
+impl<T> DerefMut for OwnedEntry<T> {
+    /// Mutably dereference the inner item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other references to the inner item exist. Use
+    /// [`OwnedEntry::get_mut`] for a non-panicking variant.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.get_mut()
+            .expect("can not mutably dereference a shared entry")
+    }
+}
+
 #[cfg(feature = "serde")]
-impl<T: Default + serde::Serialize> serde::Serialize for OwnedEntry<T> {
+impl<T: serde::Serialize> serde::Serialize for OwnedEntry<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -191,10 +714,20 @@ impl<T: Default + serde::Serialize> serde::Serialize for OwnedEntry<T> {
     }
 }
 
-impl<T: Default> OwnedEntry<T> {
+#[cfg(feature = "stable_deref")]
+unsafe impl<T> stable_deref_trait::StableDeref for OwnedEntry<T> {}
+#[cfg(feature = "stable_deref")]
+unsafe impl<T> stable_deref_trait::CloneStableDeref for OwnedEntry<T> {}
+
+impl<T> OwnedEntry<T> {
     /// Get reference to the inner item.
     pub fn get(&self) -> &T {
-        &self
+        self
+    }
+
+    /// Get the pool this entry belongs to, e.g. to pull sibling items.
+    pub fn pool(&self) -> &Arc<Pool<T>> {
+        &self.pool
     }
 
     /// Get mutable reference to the inner item if there are no other references.
@@ -205,81 +738,726 @@ impl<T: Default> OwnedEntry<T> {
 
     /// Get mutable reference to the inner item without checking for other references.
     ///
+    /// # Safety
+    ///
+    /// The caller must ensure that no other references to the inner item exist
+    /// while the returned mutable reference is alive.
     pub unsafe fn get_mut_unchecked(&mut self) -> &mut T {
         unsafe { Prc::get_mut_unchecked(self.item.as_mut().unwrap()) }
     }
-}
 
-/// A thread-safe reference-counting pointer. `Prc` stands for 'Pooled
-/// Reference Counted'. This is like `Arc`, but only used in the pool
-/// implemented in this crate.
-///
-/// **Note**: `Drop` is not implemented for `Prc<T>`. The user should carefully
-/// manage the memory of `Prc<T>`. The user should call `drop_slow` when the
-/// last reference is dropped.
-///
-/// # Thread Safety
-///
-/// `Prc<T>` uses atomic operations for its reference counting. This means that
-/// it is thread-safe.
-///
+    /// Get the number of references to the inner item, including this one.
+    pub fn strong_count(&self) -> usize {
+        self.item.as_ref().unwrap().count()
+    }
+
+    /// Get the number of times the inner item has been pulled from the pool,
+    /// including the current checkout.
+    pub fn use_count(&self) -> usize {
+        self.item.as_ref().unwrap().use_count()
+    }
+
+    /// Get the metadata attached to the inner item if the pool was built with
+    /// metadata of type `M`. Otherwise, return `None`.
+    pub fn metadata<M: Any>(&self) -> Option<&M> {
+        self.item.as_ref().unwrap().metadata()
+    }
+
+    /// Get mutable reference to the metadata attached to the inner item if it
+    /// is of type `M` and there are no other references. Otherwise, return
+    /// `None`.
+    pub fn metadata_mut<M: Any>(&mut self) -> Option<&mut M> {
+        Prc::metadata_mut(self.item.as_mut().unwrap())
+    }
+
+    /// Get how long the item has been checked out since it was pulled.
+    #[cfg(feature = "instrument")]
+    pub fn held_for(&self) -> std::time::Duration {
+        self.item.as_ref().unwrap().held_for()
+    }
+
+    /// Get mutable reference to the inner item, cloning it into a fresh item of
+    /// the pool first if other references exist (copy-on-write).
+    ///
+    /// If the pool is exhausted, the clone is an unpooled value which is
+    /// dropped instead of being recycled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(2));
+    /// let mut item = pool.pull_owned_with(|x| *x = 1).unwrap();
+    /// let snapshot = item.clone();
+    /// *item.make_mut() += 1;
+    /// assert_eq!(*item, 2);
+    /// assert_eq!(*snapshot, 1);
+    /// ```
+    pub fn make_mut(&mut self) -> &mut T
+    where
+        T: Clone,
+    {
+        if !self.item.as_ref().unwrap().is_unique() {
+            let fresh = self.pool.pull_clone_of(self.get());
+            let old = self.item.replace(fresh).unwrap();
+            self.pool.release_ref(old);
+        }
+        unsafe { self.get_mut_unchecked() }
+    }
+
+    /// Replace the inner item with `value`, returning the old one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other references to the inner item exist.
+    pub fn replace(&mut self, value: T) -> T {
+        std::mem::replace(&mut **self, value)
+    }
+
+    /// Take the inner item out, leaving a default value in the slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other references to the inner item exist.
+    pub fn take(&mut self) -> T
+    where
+        T: Default,
+    {
+        std::mem::take(&mut **self)
+    }
+
+    /// Take the inner item out if this is the only reference, leaving a default
+    /// value in the slot which is recycled. Otherwise, return the entry back.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<Vec<u8>>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned_with(|v| v.push(1)).unwrap();
+    /// assert_eq!(item.try_unwrap().ok(), Some(vec![1]));
+    /// assert!(pool.pull().unwrap().is_empty());
+    /// ```
+    pub fn try_unwrap(mut self) -> Result<T, Self>
+    where
+        T: Default,
+    {
+        match self.get_mut() {
+            Some(item) => Ok(std::mem::take(item)),
+            None => Err(self),
+        }
+    }
+
+    /// Permanently remove the inner item from the pool if this is the only
+    /// reference. Otherwise, return the entry back.
+    ///
+    /// The item no longer counts as allocated, so the pool can allocate a
+    /// replacement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned_with(|x| *x = 42).unwrap();
+    /// assert_eq!(item.detach().ok(), Some(42));
+    /// assert_eq!(pool.allocated(), 0);
+    /// ```
+    pub fn detach(mut self) -> Result<T, Self> {
+        match self.item.as_ref().unwrap().is_unique() {
+            true => Ok(self.pool.detach(self.item.take().unwrap())),
+            false => Err(self),
+        }
+    }
+
+    /// Destroy the inner item instead of returning it to the pool, e.g. when it
+    /// is known to be corrupted.
+    ///
+    /// If other references exist, the item is destroyed when the last one is
+    /// dropped. The item no longer counts as allocated afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned().unwrap();
+    /// let clone = item.clone();
+    /// item.discard();
+    /// assert_eq!(pool.allocated(), 1);
+    /// drop(clone);
+    /// assert_eq!(pool.allocated(), 0);
+    /// ```
+    pub fn discard(self) {
+        self.item.as_ref().unwrap().discard();
+    }
+
+    /// Pull a new item from the pool and clone the inner item into it. Return
+    /// [`PullError::Exhausted`] if the pool is empty.
+    pub fn duplicate(&self) -> Result<OwnedEntry<T>, PullError>
+    where
+        T: Clone,
+    {
+        self.pool.try_pull_owned().map(|mut entry| {
+            self.pool
+                .guarded(&mut entry.item, |item| item.clone_from(self.get()));
+            entry
+        })
+    }
+
+    /// Keep the inner item alive forever and get a `'static` reference to it.
+    ///
+    /// The item is never returned to the pool and no longer counts as
+    /// allocated, so the pool can allocate a replacement.
+    pub fn leak(mut self) -> &'static T
+    where
+        T: 'static,
+    {
+        self.pool.leak(self.item.take().unwrap())
+    }
+
+    /// Create an [`OwnedWeakEntry`] to the item which does not keep it checked
+    /// out.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned().unwrap();
+    /// let weak = item.downgrade();
+    /// assert!(weak.upgrade().is_some());
+    /// drop(item);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn downgrade(&self) -> OwnedWeakEntry<T> {
+        OwnedWeakEntry {
+            item: self.item.as_ref().unwrap().downgrade(),
+            pool: self.pool.clone(),
+        }
+    }
+
+    /// Get a borrowed [`Entry`] to the same item, so APIs written against
+    /// `Entry` can accept owned entries without cloning the `Arc` reference to
+    /// the pool.
+    ///
+    /// The returned entry is another reference to the item, like a clone.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Entry, Pool};
+    /// use std::sync::Arc;
+    ///
+    /// fn len(entry: Entry<'_, String>) -> usize {
+    ///     entry.len()
+    /// }
+    ///
+    /// let pool: Arc<Pool<String>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned_with(|s| s.push_str("hello")).unwrap();
+    /// assert_eq!(len(item.as_entry()), 5);
+    /// assert_eq!(pool.in_use(), 1);
+    /// ```
+    pub fn as_entry(&self) -> Entry<'_, T> {
+        Entry {
+            item: self.item.clone(),
+            pool: &self.pool,
+        }
+    }
+
+    /// Convert into a [`PoolToken`] which is a plain integer, keeping the item
+    /// checked out until the token is converted back with
+    /// [`Pool::entry_from_raw`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Pool, PoolToken};
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned_with(|x| *x = 42).unwrap();
+    /// let raw: u64 = item.into_raw().into_u64();
+    /// assert_eq!(pool.in_use(), 1);
+    /// let item = pool.entry_from_raw(PoolToken::from_u64(raw)).unwrap();
+    /// assert_eq!(*item, 42);
+    /// ```
+    pub fn into_raw(mut self) -> PoolToken
+    where
+        T: Send + Sync,
+    {
+        self.pool.store_token(self.item.take().unwrap())
+    }
+
+    /// Convert into an [`ErasedEntry`] which hides the pool and the item type,
+    /// so items of different pools can be stored in one collection.
+    pub fn into_erased(self) -> ErasedEntry
+    where
+        T: Send + Sync + 'static,
+    {
+        ErasedEntry::new(self)
+    }
+
+    /// Project the entry into a part of the item, e.g. a field, keeping the
+    /// item checked out until the returned [`OwnedMappedEntry`] is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<(u32, Vec<u8>)>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned_with(|t| t.1.push(1)).unwrap();
+    /// let body = item.map_owned(|t| t.1.as_slice());
+    /// assert_eq!(&*body, &[1]);
+    /// ```
+    pub fn map_owned<U, F>(self, f: F) -> OwnedMappedEntry<T, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&T) -> &U,
+    {
+        OwnedMappedEntry::new(self, f)
+    }
+}
+
+/// A unique entry in the pool.
+///
+/// `UniqueEntry` holds an item from the pool exclusively and a reference to the
+/// [`Pool`]. It can not be cloned, so no reference counting is involved and the
+/// item can be mutated directly.
+///
+/// `UniqueEntry` is `Send` when `T` is `Send`, so pools of `Send + !Sync`
+/// types, e.g. containing a `Cell`, can be shared between threads pulling
+/// unique entries.
+/// When the `UniqueEntry` is dropped, the item is returned to the pool.
+///
+pub struct UniqueEntry<'a, T> {
+    // `item` is always `Some` before the entry is dropped.
+    pub(crate) item: Option<Prc<T>>,
+    pub(crate) pool: &'a Pool<T>,
+}
+
+impl<'a, T: PartialEq> PartialEq for UniqueEntry<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.item.eq(&other.item)
+    }
+}
+
+impl<'a, T: Eq> Eq for UniqueEntry<'a, T> {}
+
+impl<'a, T: PartialOrd> PartialOrd for UniqueEntry<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.item.partial_cmp(&other.item)
+    }
+}
+
+impl<'a, T: Ord> Ord for UniqueEntry<'a, T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.item.cmp(&other.item)
+    }
+}
+
+impl<'a, T: Hash> Hash for UniqueEntry<'a, T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.get().hash(state)
+    }
+}
+
+impl<'a, T: Debug> Debug for UniqueEntry<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<'a, T: Display> Display for UniqueEntry<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<'a, T> AsRef<T> for UniqueEntry<'a, T> {
+    fn as_ref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<'a, T> Borrow<T> for UniqueEntry<'a, T> {
+    fn borrow(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<'a> AsRef<[u8]> for UniqueEntry<'a, Vec<u8>> {
+    fn as_ref(&self) -> &[u8] {
+        self.get()
+    }
+}
+
+impl<'a> AsRef<[u8]> for UniqueEntry<'a, String> {
+    fn as_ref(&self) -> &[u8] {
+        self.get().as_bytes()
+    }
+}
+
+impl<'a> AsRef<str> for UniqueEntry<'a, String> {
+    fn as_ref(&self) -> &str {
+        self.get()
+    }
+}
+
+// The item is held exclusively, so moving the entry to another thread only
+// requires `T: Send`, which also makes the pool `Sync`.
+unsafe impl<'a, T: Send> Send for UniqueEntry<'a, T> {}
+
+impl<'a, T> Drop for UniqueEntry<'a, T> {
+    fn drop(&mut self) {
+        if let Some(item) = self.item.take() {
+            self.pool.recycle(item);
+        }
+    }
+}
+
+impl<'a, T> Deref for UniqueEntry<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        self.item.as_ref().unwrap()
+    }
+}
+
+impl<'a, T> DerefMut for UniqueEntry<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { Prc::get_mut_unchecked(self.item.as_mut().unwrap()) }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T: serde::Serialize> serde::Serialize for UniqueEntry<'a, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.get().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "stable_deref")]
+unsafe impl<'a, T> stable_deref_trait::StableDeref for UniqueEntry<'a, T> {}
+
+impl<'a, T> UniqueEntry<'a, T> {
+    /// Get reference to the inner item.
+    pub fn get(&self) -> &T {
+        self
+    }
+
+    /// Get the pool this entry belongs to.
+    pub fn pool(&self) -> &'a Pool<T> {
+        self.pool
+    }
+
+    /// Get the number of times the inner item has been pulled from the pool,
+    /// including the current checkout.
+    pub fn use_count(&self) -> usize {
+        self.item.as_ref().unwrap().use_count()
+    }
+
+    /// Get the metadata attached to the inner item if the pool was built with
+    /// metadata of type `M`. Otherwise, return `None`.
+    pub fn metadata<M: Any>(&self) -> Option<&M> {
+        self.item.as_ref().unwrap().metadata()
+    }
+
+    /// Get mutable reference to the metadata attached to the inner item if it
+    /// is of type `M`. Otherwise, return `None`.
+    pub fn metadata_mut<M: Any>(&mut self) -> Option<&mut M> {
+        Prc::metadata_mut(self.item.as_mut().unwrap())
+    }
+
+    /// Get how long the item has been checked out since it was pulled.
+    #[cfg(feature = "instrument")]
+    pub fn held_for(&self) -> std::time::Duration {
+        self.item.as_ref().unwrap().held_for()
+    }
+
+    /// Get mutable reference to the inner item.
+    pub fn get_mut(&mut self) -> &mut T {
+        self
+    }
+
+    /// Replace the inner item with `value`, returning the old one.
+    pub fn replace(&mut self, value: T) -> T {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Take the inner item out, leaving a default value in the slot.
+    pub fn take(&mut self) -> T
+    where
+        T: Default,
+    {
+        std::mem::take(self.get_mut())
+    }
+
+    /// Permanently remove the inner item from the pool.
+    ///
+    /// The item no longer counts as allocated, so the pool can allocate a
+    /// replacement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(1);
+    /// let mut item = pool.pull_unique().unwrap();
+    /// item.push(1);
+    /// assert_eq!(item.detach(), vec![1]);
+    /// assert_eq!(pool.allocated(), 0);
+    /// ```
+    pub fn detach(mut self) -> T {
+        self.pool.detach(self.item.take().unwrap())
+    }
+
+    /// Destroy the inner item instead of returning it to the pool, e.g. when it
+    /// is known to be corrupted. The item no longer counts as allocated.
+    pub fn discard(mut self) {
+        self.pool.discard(self.item.take().unwrap());
+    }
+
+    /// Split the item into two mutable projections over disjoint parts of
+    /// it, e.g. the header and the body of a message, which can be used
+    /// independently, even from different threads. The item is returned to the
+    /// pool once both are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// #[derive(Default)]
+    /// struct Message {
+    ///     header: Vec<u8>,
+    ///     body: Vec<u8>,
+    /// }
+    ///
+    /// let pool: Pool<Message> = Pool::with_capacity(1);
+    /// let item = pool.pull_unique().unwrap();
+    /// let (mut header, mut body) = item.split_mut(|m| (&mut m.header, &mut m.body));
+    /// std::thread::scope(|s| {
+    ///     s.spawn(move || header.extend_from_slice(b"head"));
+    ///     s.spawn(move || body.extend_from_slice(b"body"));
+    /// });
+    /// let item = pool.pull().unwrap();
+    /// assert_eq!(item.header, b"head");
+    /// assert_eq!(item.body, b"body");
+    /// ```
+    pub fn split_mut<U, V, F>(self, f: F) -> (MappedMutEntry<'a, T, U>, MappedMutEntry<'a, T, V>)
+    where
+        U: ?Sized,
+        V: ?Sized,
+        F: FnOnce(&mut T) -> (&mut U, &mut V),
+    {
+        MappedMutEntry::split(self, f)
+    }
+
+    /// Convert into a shared [`Entry`] which can be cloned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<String> = Pool::with_capacity(1);
+    /// let mut item = pool.pull_unique().unwrap();
+    /// item.push_str("hello");
+    /// let shared = item.into_shared();
+    /// let shared_clone = shared.clone();
+    /// assert_eq!(&*shared_clone, "hello");
+    /// drop(shared);
+    /// drop(shared_clone);
+    /// assert_eq!(pool.available(), 1);
+    /// ```
+    pub fn into_shared(mut self) -> Entry<'a, T> {
+        let item = self.item.take().unwrap();
+        item.inc_ref();
+        Entry {
+            item: Some(item),
+            pool: self.pool,
+        }
+    }
+}
+
+/// A weak reference to an item checked out from the pool.
+///
+/// Created by [`Entry::downgrade`]. A `WeakEntry` does not keep the item
+/// checked out, so it can be stored in caches and indexes without preventing
+/// the item from being recycled. Once the item has gone back to the pool,
+/// [`upgrade`](WeakEntry::upgrade) returns `None`, even if the same slot is
+/// pulled again.
+///
+/// While a weak reference to the current checkout exists, the item is not
+/// considered unique, so methods such as [`Entry::get_mut`] and
+/// [`Entry::detach`] fail.
+pub struct WeakEntry<'a, T> {
+    pub(crate) item: PrcWeak<T>,
+    pub(crate) pool: &'a Pool<T>,
+}
+
+impl<'a, T> Clone for WeakEntry<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            item: self.item.clone(),
+            pool: self.pool,
+        }
+    }
+}
+
+impl<'a, T> Debug for WeakEntry<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("(WeakEntry)")
+    }
+}
+
+impl<'a, T> WeakEntry<'a, T> {
+    /// Get a new [`Entry`] to the item if it is still checked out. Otherwise,
+    /// return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(1);
+    /// let item = pool.pull_with(|x| *x = 42).unwrap();
+    /// let weak = item.downgrade();
+    /// assert_eq!(weak.upgrade().as_deref(), Some(&42));
+    /// drop(item);
+    /// assert!(weak.upgrade().is_none());
+    /// let _item = pool.pull().unwrap();
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn upgrade(&self) -> Option<Entry<'a, T>> {
+        self.item.upgrade().map(|item| Entry {
+            item: Some(item),
+            pool: self.pool,
+        })
+    }
+}
+
+/// A weak reference to an item checked out from the pool, holding an `Arc`
+/// reference to the [`Pool`].
+///
+/// Created by [`OwnedEntry::downgrade`]. See [`WeakEntry`] for details.
+pub struct OwnedWeakEntry<T> {
+    pub(crate) item: PrcWeak<T>,
+    pub(crate) pool: Arc<Pool<T>>,
+}
+
+impl<T> Clone for OwnedWeakEntry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            item: self.item.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<T> Debug for OwnedWeakEntry<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("(OwnedWeakEntry)")
+    }
+}
+
+impl<T> OwnedWeakEntry<T> {
+    /// Get a new [`OwnedEntry`] to the item if it is still checked out.
+    /// Otherwise, return `None`.
+    pub fn upgrade(&self) -> Option<OwnedEntry<T>> {
+        self.item.upgrade().map(|item| OwnedEntry {
+            item: Some(item),
+            pool: self.pool.clone(),
+        })
+    }
+}
+
+/// A thread-safe reference-counting pointer. `Prc` stands for 'Pooled
+/// Reference Counted'. This is like `Arc`, but only used in the pool
+/// implemented in this crate.
+///
+/// **Note**: `Drop` is not implemented for `Prc<T>`. The user should carefully
+/// manage the memory of `Prc<T>`. The user should call `drop_slow` when the
+/// last reference is dropped.
+///
+/// # Thread Safety
+///
+/// `Prc<T>` uses atomic operations for its reference counting. This means that
+/// it is thread-safe.
+///
 /// # Cloning references
 ///
 /// Creating a new reference from an existing reference-counted pointer is done using the
 /// `Clone` trait implemented for [`Prc<T>`][Prc].
 ///
-pub(crate) struct Prc<T: ?Sized> {
+pub(crate) struct Prc<T> {
     ptr: NonNull<PrcInner<T>>,
 }
 
-unsafe impl<T: ?Sized + Send + Sync> Send for Prc<T> {}
-unsafe impl<T: ?Sized + Send + Sync> Sync for Prc<T> {}
+unsafe impl<T: Send + Sync> Send for Prc<T> {}
+unsafe impl<T: Send + Sync> Sync for Prc<T> {}
 
-impl<T: ?Sized> Deref for Prc<T> {
+impl<T> Deref for Prc<T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
-        &self.inner().data
+        self.data()
     }
 }
 
-impl<T: ?Sized + PartialEq> PartialEq for Prc<T> {
+impl<T: PartialEq> PartialEq for Prc<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.inner().data.eq(other)
+        self.data().eq(other.data())
     }
 }
-impl<T: ?Sized + Eq> Eq for Prc<T> {}
+impl<T: Eq> Eq for Prc<T> {}
 
-impl<T: ?Sized + PartialOrd> PartialOrd for Prc<T> {
+impl<T: PartialOrd> PartialOrd for Prc<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.inner().data.partial_cmp(other)
+        self.data().partial_cmp(other.data())
     }
 }
 
-impl<T: ?Sized + Ord> Ord for Prc<T> {
+impl<T: Ord> Ord for Prc<T> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.inner().data.cmp(other)
+        self.data().cmp(other.data())
     }
 }
 
-impl<T: ?Sized + Hash> Hash for Prc<T> {
+impl<T: Hash> Hash for Prc<T> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.inner().data.hash(state)
+        self.data().hash(state)
     }
 }
 
-impl<T: ?Sized + Debug> Debug for Prc<T> {
+impl<T: Debug> Debug for Prc<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.inner().data.fmt(f)
+        self.data().fmt(f)
     }
 }
 
-impl<T: ?Sized + Display> Display for Prc<T> {
+impl<T: Display> Display for Prc<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.inner().data.fmt(f)
+        self.data().fmt(f)
     }
 }
 
-impl<T: ?Sized> Clone for Prc<T> {
+impl<T> Clone for Prc<T> {
     fn clone(&self) -> Self {
         self.inc_ref();
         Self { ptr: self.ptr }
@@ -290,58 +1468,311 @@ impl<T> Prc<T> {
     /// Starting the pointer count as 0 which means it is in the pool without
     /// any clone instance.
     #[inline]
-    pub(crate) fn new_zero(data: T) -> Self {
-        let x: Box<_> = Box::new(PrcInner {
-            count: AtomicUsize::new(0),
-            data,
-        });
-        Self {
-            ptr: Box::leak(x).into(),
-        }
+    pub(crate) fn new_zero(data: T, metadata: Option<Metadata>) -> Self {
+        Self::from_inner(PrcInner::new(0, true, data, metadata))
+    }
+
+    /// Create a new `Prc<T>` in the pool whose data is constructed on first
+    /// use with [`Prc::init`].
+    #[inline]
+    pub(crate) fn new_uninit(metadata: Option<Metadata>) -> Self {
+        Self::from_inner(PrcInner::with_data(
+            0,
+            true,
+            MaybeUninit::uninit(),
+            FLAG_UNINIT,
+            metadata,
+        ))
     }
 
     /// Create a new `Prc<T>` with the reference count starting at 1.
     #[inline]
-    pub(crate) fn new(data: T) -> Self {
-        let x: Box<_> = Box::new(PrcInner {
-            count: AtomicUsize::new(1),
-            data,
-        });
+    pub(crate) fn new(data: T, metadata: Option<Metadata>) -> Self {
+        let item = Self::from_inner(PrcInner::new(1, true, data, metadata));
+        item.checkout();
+        item
+    }
+
+    /// Create a new `Prc<T>` which does not belong to the pool, with the
+    /// reference count starting at 1. It is dropped instead of being recycled
+    /// when the last reference is dropped.
+    #[inline]
+    pub(crate) fn new_unpooled(data: T, metadata: Option<Metadata>) -> Self {
+        let item = Self::from_inner(PrcInner::new(1, false, data, metadata));
+        item.checkout();
+        item
+    }
+
+    #[inline]
+    fn from_inner(inner: PrcInner<T>) -> Self {
+        let x: Box<_> = Box::new(inner);
         Self {
             ptr: Box::leak(x).into(),
         }
     }
 }
 
-impl<T: ?Sized> Prc<T> {
+impl<T> Prc<T> {
     /// Increase the reference count and return the previous count.
     #[inline]
     pub(crate) fn inc_ref(&self) -> usize {
-        self.inner().count.fetch_add(1, Relaxed)
+        (self.inner().state.fetch_add(1, Relaxed) & COUNT_MASK) as usize
     }
 
     /// Decrease the reference count and return the previous count.
     #[inline]
     pub(crate) fn dec_ref(&self) -> usize {
-        self.inner().count.fetch_sub(1, Release)
+        (self.inner().state.fetch_sub(1, Release) & COUNT_MASK) as usize
+    }
+
+    /// Get the address of the item, which identifies it while it is allocated.
+    #[cfg(feature = "instrument")]
+    pub(crate) fn addr(&self) -> usize {
+        self.ptr.as_ptr() as usize
+    }
+
+    /// Mark the item as being in the idle queue, and return whether it already
+    /// was.
+    #[cfg(feature = "paranoid")]
+    pub(crate) fn mark_queued(&self) -> bool {
+        self.inner().flags.fetch_or(FLAG_QUEUED, Relaxed) & FLAG_QUEUED != 0
+    }
+
+    /// Mark the item as taken out of the idle queue.
+    #[cfg(feature = "paranoid")]
+    pub(crate) fn unmark_queued(&self) {
+        self.inner().flags.fetch_and(!FLAG_QUEUED, Relaxed);
+    }
+
+    /// Record `pool` as the owner of the item, with a slot index from `slot`,
+    /// if it has no owner yet. Return the owner and slot index of the item.
+    #[cfg(feature = "paranoid")]
+    pub(crate) fn claim_owner(&self, pool: usize, slot: impl FnOnce() -> usize) -> (usize, usize) {
+        let inner = self.inner();
+        match inner.owner.compare_exchange(0, pool, AcqRel, Acquire) {
+            Ok(_) => {
+                let slot = slot();
+                inner.slot.store(slot, Release);
+                (pool, slot)
+            }
+            Err(owner) => (owner, inner.slot.load(Acquire)),
+        }
+    }
+
+    /// Get the slot index of the item in its owning pool.
+    #[cfg(feature = "paranoid")]
+    pub(crate) fn slot(&self) -> usize {
+        self.inner().slot.load(Acquire)
+    }
+
+    /// Record that the item is handed out by a pull. Must only be called while
+    /// the item is held exclusively.
+    #[inline]
+    pub(crate) fn checkout(&self) {
+        self.inner().uses.fetch_add(1, Relaxed);
+        #[cfg(feature = "instrument")]
+        unsafe {
+            *self.inner().pulled_at.get() = std::time::Instant::now();
+        }
+    }
+
+    /// Get the time elapsed since the item was last pulled.
+    #[cfg(feature = "instrument")]
+    pub(crate) fn held_for(&self) -> std::time::Duration {
+        unsafe { *self.inner().pulled_at.get() }.elapsed()
+    }
+
+    /// Record that the item is returned to the pool. Must only be called while
+    /// the item is held exclusively.
+    #[inline]
+    pub(crate) fn mark_recycled(&self) {
+        unsafe { *self.inner().recycled_at.get() = std::time::Instant::now() };
+    }
+
+    /// Get the time elapsed since the item was last recycled, or created. Must
+    /// only be called while the item is held exclusively.
+    #[inline]
+    pub(crate) fn idle_for(&self) -> std::time::Duration {
+        unsafe { *self.inner().recycled_at.get() }.elapsed()
+    }
+
+    /// Get the time elapsed since the value of the item was created.
+    #[inline]
+    pub(crate) fn age(&self) -> std::time::Duration {
+        self.inner().created_at.elapsed()
+    }
+
+    /// Get the number of times the item has been pulled.
+    #[inline]
+    pub(crate) fn use_count(&self) -> usize {
+        self.inner().uses.load(Relaxed)
+    }
+
+    /// Get the metadata attached to the item if it is of type `M`.
+    #[inline]
+    pub(crate) fn metadata<M: Any>(&self) -> Option<&M> {
+        self.inner().metadata.as_ref()?.downcast_ref()
+    }
+
+    /// Get mutable reference to the metadata attached to the item if it is of
+    /// type `M` and the item is unique.
+    #[inline]
+    pub(crate) fn metadata_mut<M: Any>(this: &mut Self) -> Option<&mut M> {
+        if !this.is_unique() {
+            return None;
+        }
+        unsafe { (*this.ptr.as_ptr()).metadata.as_mut()?.downcast_mut() }
+    }
+
+    /// Get the current reference count.
+    #[inline]
+    pub(crate) fn count(&self) -> usize {
+        (self.inner().state.load(Acquire) & COUNT_MASK) as usize
+    }
+
+    /// Whether the item belongs to the pool.
+    #[inline]
+    pub(crate) fn is_pooled(&self) -> bool {
+        self.inner().pooled
+    }
+
+    /// Mark the item to be destroyed instead of recycled.
+    #[inline]
+    pub(crate) fn discard(&self) {
+        self.inner().flags.fetch_or(FLAG_DISCARDED, Relaxed);
+    }
+
+    /// Whether the item should be destroyed instead of recycled.
+    #[inline]
+    pub(crate) fn is_discarded(&self) -> bool {
+        self.inner().flags.load(Relaxed) & FLAG_DISCARDED != 0
+    }
+
+    /// Mark the item to skip the clear function on its next recycle.
+    #[inline]
+    pub(crate) fn skip_clear(&self) {
+        self.inner().flags.fetch_or(FLAG_SKIP_CLEAR, Relaxed);
+    }
+
+    /// Whether the clear function should be skipped on this recycle, resetting
+    /// the mark for the next one.
+    #[inline]
+    pub(crate) fn take_skip_clear(&self) -> bool {
+        self.inner().flags.fetch_and(!FLAG_SKIP_CLEAR, Relaxed) & FLAG_SKIP_CLEAR != 0
+    }
+
+    /// Start a new generation, invalidating all weak pointers to the previous
+    /// one. Must only be called while the reference count is 0.
+    #[inline]
+    pub(crate) fn next_generation(&self) {
+        let state = &self.inner().state;
+        let next = generation_of(state.load(Relaxed)).wrapping_add(1);
+        self.inner()
+            .live_weak
+            .store((next as u64) << GEN_SHIFT, Relaxed);
+        state.store((next as u64) << GEN_SHIFT, Release);
+    }
+
+    /// Create a weak pointer to the current generation of the item.
+    pub(crate) fn downgrade(&self) -> PrcWeak<T> {
+        let inner = self.inner();
+        inner.weak.fetch_add(1, Relaxed);
+        let generation = generation_of(inner.state.load(Relaxed));
+        inner.adjust_live_weak(generation, |count| count + 1);
+        PrcWeak {
+            ptr: self.ptr,
+            generation,
+        }
+    }
+
+    /// Mark the item as pinned until its next recycle.
+    #[inline]
+    pub(crate) fn pin(&self) {
+        self.inner().flags.fetch_or(FLAG_PINNED, Relaxed);
+    }
+
+    /// Whether the item was pinned, resetting the mark for the next checkout.
+    #[inline]
+    pub(crate) fn take_pinned(&self) -> bool {
+        self.inner().flags.fetch_and(!FLAG_PINNED, Relaxed) & FLAG_PINNED != 0
+    }
+
+    /// Mark the item as leaked, returning whether it was not leaked before.
+    #[inline]
+    pub(crate) fn mark_leaked(&self) -> bool {
+        self.inner().flags.fetch_or(FLAG_LEAKED, Relaxed) & FLAG_LEAKED == 0
     }
 
-    /// Drops the inner data.
+    /// Forget this reference without decreasing the reference count, so the
+    /// item is never recycled or dropped.
+    pub(crate) fn leak(self) -> &'static T
+    where
+        T: 'static,
+    {
+        unsafe { (*self.ptr.as_ptr()).data.assume_init_ref() }
+    }
+
+    /// Moves the inner data out, the allocation is freed once no weak pointers
+    /// remain.
+    pub(crate) unsafe fn into_inner(self) -> T {
+        self.next_generation();
+        let data = unsafe { (*self.ptr.as_ptr()).data.assume_init_read() };
+        unsafe { release_weak(self.ptr) };
+        data
+    }
+
+    /// Drops the inner data, the allocation is freed once no weak pointers
+    /// remain.
     pub(crate) unsafe fn drop_slow(&self) {
+        self.next_generation();
         unsafe {
-            drop(Box::from_raw(self.ptr.as_ptr()));
+            if !self.is_uninit() {
+                (*self.ptr.as_ptr()).data.assume_init_drop();
+            }
+            release_weak(self.ptr);
         }
     }
 
+    /// Get a reference to the inner data, which is initialized unless the item
+    /// is idle in the pool.
+    #[inline]
+    fn data(&self) -> &T {
+        unsafe { self.inner().data.assume_init_ref() }
+    }
+
     #[inline]
     pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
-        unsafe { &mut (*this.ptr.as_ptr()).data }
+        unsafe { (*this.ptr.as_ptr()).data.assume_init_mut() }
+    }
+
+    /// Whether the data of the item has not been constructed yet.
+    #[inline]
+    pub(crate) fn is_uninit(&self) -> bool {
+        self.inner().flags.load(Relaxed) & FLAG_UNINIT != 0
+    }
+
+    /// Construct the data of an uninitialized item. Must only be called while
+    /// the item is not shared.
+    #[inline]
+    pub(crate) unsafe fn init(this: &mut Self, data: T) {
+        let inner = unsafe { &mut *this.ptr.as_ptr() };
+        inner.data.write(data);
+        inner.created_at = std::time::Instant::now();
+        inner.flags.fetch_and(!FLAG_UNINIT, Relaxed);
+    }
+
+    /// Whether only one reference exists or the item is in the pool, and no
+    /// weak pointer can be upgraded to share it.
+    #[inline]
+    pub(crate) fn is_unique(&self) -> bool {
+        let inner = self.inner();
+        inner.state.load(Acquire) & COUNT_MASK <= 1
+            && inner.live_weak.load(Acquire) & COUNT_MASK == 0
     }
 
     #[inline]
     pub fn get_mut(this: &mut Self) -> Option<&mut T> {
-        // Only one reference exists or in the pool.
-        if this.inner().count.load(Acquire) <= 1 {
+        if this.is_unique() {
             unsafe { Some(Prc::get_mut_unchecked(this)) }
         } else {
             None
@@ -354,10 +1785,171 @@ impl<T: ?Sized> Prc<T> {
     }
 }
 
-struct PrcInner<T: ?Sized> {
-    count: AtomicUsize,
-    data: T,
+/// A weak pointer to one generation of a [`Prc<T>`].
+///
+/// It keeps the allocation alive but not the item checked out, and can only
+/// be upgraded while the generation it was created for is still in use.
+pub(crate) struct PrcWeak<T> {
+    ptr: NonNull<PrcInner<T>>,
+    generation: u32,
+}
+
+unsafe impl<T: Send + Sync> Send for PrcWeak<T> {}
+unsafe impl<T: Send + Sync> Sync for PrcWeak<T> {}
+
+impl<T> PrcWeak<T> {
+    /// Increase the reference count if the generation is still in use.
+    pub(crate) fn upgrade(&self) -> Option<Prc<T>> {
+        let state = unsafe { &self.ptr.as_ref().state };
+        let mut current = state.load(Relaxed);
+        loop {
+            if current & COUNT_MASK == 0 || generation_of(current) != self.generation {
+                return None;
+            }
+            match state.compare_exchange_weak(current, current + 1, Acquire, Relaxed) {
+                Ok(_) => return Some(Prc { ptr: self.ptr }),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+impl<T> Clone for PrcWeak<T> {
+    fn clone(&self) -> Self {
+        let inner = unsafe { self.ptr.as_ref() };
+        inner.weak.fetch_add(1, Relaxed);
+        inner.adjust_live_weak(self.generation, |count| count + 1);
+        Self {
+            ptr: self.ptr,
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T> Drop for PrcWeak<T> {
+    fn drop(&mut self) {
+        unsafe { self.ptr.as_ref() }.adjust_live_weak(self.generation, |count| count - 1);
+        unsafe { release_weak(self.ptr) };
+    }
+}
+
+/// Release one weak count, freeing the allocation if it was the last one.
+///
+/// # Safety
+///
+/// The inner data must have been dropped or moved out if this releases the
+/// count held on behalf of the item itself.
+unsafe fn release_weak<T>(ptr: NonNull<PrcInner<T>>) {
+    if unsafe { ptr.as_ref() }.weak.fetch_sub(1, Release) == 1 {
+        fence(Acquire);
+        unsafe { drop(Box::from_raw(ptr.as_ptr())) };
+    }
+}
+
+struct PrcInner<T> {
+    /// Reference count in the low 32 bits, and the generation above it.
+    state: AtomicU64,
+    /// Number of weak pointers to the current generation in the low 32 bits,
+    /// and the generation above it. Weak pointers to previous generations no
+    /// longer count.
+    live_weak: AtomicU64,
+    /// Number of weak pointers, plus one held on behalf of the item until its
+    /// data is dropped.
+    weak: AtomicUsize,
+    /// Whether the item belongs to the pool and should be recycled.
+    pooled: bool,
+    /// State flags of the item, see `FLAG_*` constants.
+    flags: AtomicU8,
+    /// Number of times the item has been pulled.
+    uses: AtomicUsize,
+    /// User metadata attached to the item, only mutated while it is held
+    /// exclusively.
+    metadata: Option<Metadata>,
+    /// When the item was last pulled, only written while it is held exclusively.
+    #[cfg(feature = "instrument")]
+    pulled_at: std::cell::UnsafeCell<std::time::Instant>,
+    /// Identifier of the pool owning the item, or 0 until it is recycled.
+    #[cfg(feature = "paranoid")]
+    owner: AtomicUsize,
+    /// Index of the item among the items of its owning pool.
+    #[cfg(feature = "paranoid")]
+    slot: AtomicUsize,
+    /// When the value of the item was created.
+    created_at: std::time::Instant,
+    /// When the item was last recycled, or created, only written while it is
+    /// held exclusively.
+    recycled_at: std::cell::UnsafeCell<std::time::Instant>,
+    /// Initialized unless `FLAG_UNINIT` is set.
+    data: MaybeUninit<T>,
+}
+
+/// User metadata attached to each item of a pool.
+pub(crate) type Metadata = Box<dyn Any + Send + Sync>;
+
+/// The item should be destroyed instead of recycled.
+const FLAG_DISCARDED: u8 = 1;
+/// The clear function should be skipped when the item is recycled.
+const FLAG_SKIP_CLEAR: u8 = 1 << 1;
+/// The item is referenced forever and no longer counts as allocated.
+const FLAG_LEAKED: u8 = 1 << 2;
+/// The item is pinned and must be dropped in place before it is reused.
+const FLAG_PINNED: u8 = 1 << 3;
+/// The data of the item has not been constructed yet.
+const FLAG_UNINIT: u8 = 1 << 4;
+/// The item is in the idle queue, only tracked with the `paranoid` feature.
+#[cfg(feature = "paranoid")]
+const FLAG_QUEUED: u8 = 1 << 5;
+
+const COUNT_MASK: u64 = u32::MAX as u64;
+const GEN_SHIFT: u32 = 32;
+
+#[inline]
+fn generation_of(state: u64) -> u32 {
+    (state >> GEN_SHIFT) as u32
+}
+
+impl<T> PrcInner<T> {
+    fn new(count: usize, pooled: bool, data: T, metadata: Option<Metadata>) -> Self {
+        Self::with_data(count, pooled, MaybeUninit::new(data), 0, metadata)
+    }
+
+    fn with_data(
+        count: usize,
+        pooled: bool,
+        data: MaybeUninit<T>,
+        flags: u8,
+        metadata: Option<Metadata>,
+    ) -> Self {
+        Self {
+            state: AtomicU64::new(count as u64),
+            live_weak: AtomicU64::new(0),
+            weak: AtomicUsize::new(1),
+            pooled,
+            flags: AtomicU8::new(flags),
+            uses: AtomicUsize::new(0),
+            metadata,
+            #[cfg(feature = "instrument")]
+            pulled_at: std::cell::UnsafeCell::new(std::time::Instant::now()),
+            #[cfg(feature = "paranoid")]
+            owner: AtomicUsize::new(0),
+            #[cfg(feature = "paranoid")]
+            slot: AtomicUsize::new(0),
+            created_at: std::time::Instant::now(),
+            recycled_at: std::cell::UnsafeCell::new(std::time::Instant::now()),
+            data,
+        }
+    }
+}
+
+impl<T> PrcInner<T> {
+    /// Apply `func` to the number of weak pointers to `generation`, unless it
+    /// is no longer the current generation.
+    fn adjust_live_weak(&self, generation: u32, func: fn(u64) -> u64) {
+        let _ = self.live_weak.fetch_update(Release, Relaxed, |current| {
+            (generation_of(current) == generation).then(|| func(current))
+        });
+    }
 }
 
-unsafe impl<T: ?Sized + Send + Sync> Send for PrcInner<T> {}
-unsafe impl<T: ?Sized + Send + Sync> Sync for PrcInner<T> {}
+unsafe impl<T: Send + Sync> Send for PrcInner<T> {}
+unsafe impl<T: Send + Sync> Sync for PrcInner<T> {}
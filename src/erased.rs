@@ -0,0 +1,97 @@
+use std::any::Any;
+use std::fmt::Debug;
+use std::ops::Deref;
+
+use crate::OwnedEntry;
+
+/// A type-erased [`OwnedEntry`], hiding the pool and the item type.
+///
+/// Created by [`OwnedEntry::into_erased`]. It allows to store pooled items of
+/// different types in one collection and downcast them on use. When the last
+/// reference is dropped, the item is returned to its pool.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{ErasedEntry, OwnedEntry, Pool};
+/// use std::sync::Arc;
+///
+/// let strings: Arc<Pool<String>> = Arc::new(Pool::with_capacity(1));
+/// let numbers: Arc<Pool<u64>> = Arc::new(Pool::with_capacity(1));
+/// let items: Vec<ErasedEntry> = vec![
+///     strings.pull_owned_with(|s| s.push_str("hello")).unwrap().into_erased(),
+///     numbers.pull_owned_with(|x| *x = 42).unwrap().into_erased(),
+/// ];
+/// assert_eq!(items[0].downcast_ref::<String>().unwrap(), "hello");
+/// assert_eq!(items[1].downcast_ref::<u64>(), Some(&42));
+///
+/// let number: OwnedEntry<u64> = items.into_iter().nth(1).unwrap().downcast().unwrap();
+/// assert_eq!(*number, 42);
+/// assert_eq!(strings.available(), 1);
+/// ```
+pub struct ErasedEntry {
+    entry: Box<dyn Erased>,
+}
+
+/// Object-safe view of an [`OwnedEntry`] of any item type.
+trait Erased: Send + Sync {
+    fn value(&self) -> &(dyn Any + Send);
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Send + Sync + 'static> Erased for OwnedEntry<T> {
+    fn value(&self) -> &(dyn Any + Send) {
+        self.get()
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl ErasedEntry {
+    pub(crate) fn new<T: Send + Sync + 'static>(entry: OwnedEntry<T>) -> Self {
+        Self {
+            entry: Box::new(entry),
+        }
+    }
+
+    /// Whether the inner item is of type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.entry.value().is::<T>()
+    }
+
+    /// Get a reference to the inner item if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.entry.value().downcast_ref()
+    }
+
+    /// Convert back into the typed [`OwnedEntry`] if the inner item is of type
+    /// `T`. Otherwise, return the erased entry back.
+    pub fn downcast<T: Send + Sync + 'static>(self) -> Result<OwnedEntry<T>, Self> {
+        if self.is::<T>() {
+            Ok(*self.entry.into_any().downcast().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> From<OwnedEntry<T>> for ErasedEntry {
+    fn from(entry: OwnedEntry<T>) -> Self {
+        Self::new(entry)
+    }
+}
+
+impl Deref for ErasedEntry {
+    type Target = dyn Any + Send;
+    fn deref(&self) -> &Self::Target {
+        self.entry.value()
+    }
+}
+
+impl Debug for ErasedEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErasedEntry").finish_non_exhaustive()
+    }
+}
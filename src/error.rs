@@ -0,0 +1,183 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+/// Error returned when an item can not be pulled from the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PullError {
+    /// All items of the pool are in use and the capacity is reached.
+    Exhausted,
+    /// No item became available before the timeout elapsed.
+    Timeout,
+    /// The blocking pull was aborted by an [`Interrupter`](crate::Interrupter).
+    Interrupted,
+    /// The pool has been closed with [`Pool::close`](crate::Pool::close).
+    Closed,
+    /// The factory set with [`Builder::try_factory`](crate::Builder::try_factory)
+    /// failed to create a new item.
+    Factory(FactoryError),
+}
+
+impl Display for PullError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PullError::Exhausted => f.write_str("pool is exhausted"),
+            PullError::Timeout => f.write_str("timed out waiting for an available item"),
+            PullError::Interrupted => {
+                f.write_str("interrupted while waiting for an available item")
+            }
+            PullError::Closed => f.write_str("pool is closed"),
+            PullError::Factory(err) => write!(f, "failed to create a new item: {err}"),
+        }
+    }
+}
+
+impl Error for PullError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PullError::Factory(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned when a pool configuration is invalid.
+///
+/// Returned by [`Builder::try_build`](crate::Builder::try_build) and
+/// [`Config::validate`](crate::Config::validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// Items are preallocated in a pool with no capacity.
+    ZeroCapacityWithPrealloc {
+        /// Number of items to preallocate.
+        prealloc: usize,
+    },
+    /// More items are preallocated than the capacity of the pool.
+    PreallocExceedsCapacity {
+        /// Number of items to preallocate.
+        prealloc: usize,
+        /// Maximum capacity of the pool.
+        capacity: usize,
+    },
+    /// The idle threshold to judge a `surplus-pull` is larger than the capacity,
+    /// so reclamation can never be triggered.
+    IdleThresholdExceedsCapacity {
+        /// Threshold for idle items to judge as a `surplus-pull`.
+        threshold: usize,
+        /// Maximum capacity of the pool.
+        capacity: usize,
+    },
+    /// The low idle watermark is larger than the high one, or the high one is
+    /// larger than the capacity.
+    InvalidWatermarks {
+        /// Number of idle items at which reclamation stops.
+        low: usize,
+        /// Number of idle items above which reclamation starts.
+        high: usize,
+        /// Maximum capacity of the pool.
+        capacity: usize,
+    },
+    /// The soft capacity is larger than the capacity.
+    SoftCapacityExceedsCapacity {
+        /// Soft limit of allocated items.
+        soft_capacity: usize,
+        /// Maximum capacity of the pool.
+        capacity: usize,
+    },
+    /// The minimum number of idle items is larger than the capacity.
+    MinIdleExceedsCapacity {
+        /// Minimum number of idle items.
+        min_idle: usize,
+        /// Maximum capacity of the pool.
+        capacity: usize,
+    },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::ZeroCapacityWithPrealloc { prealloc } => {
+                write!(f, "can not preallocate {prealloc} items with zero capacity")
+            }
+            ConfigError::PreallocExceedsCapacity { prealloc, capacity } => write!(
+                f,
+                "prealloc ({prealloc}) must be less than or equal to capacity ({capacity})"
+            ),
+            ConfigError::IdleThresholdExceedsCapacity {
+                threshold,
+                capacity,
+            } => write!(
+                f,
+                "idle threshold for surplus-pull ({threshold}) exceeds capacity ({capacity})"
+            ),
+            ConfigError::InvalidWatermarks {
+                low,
+                high,
+                capacity,
+            } => write!(
+                f,
+                "idle watermarks must satisfy low ({low}) <= high ({high}) <= capacity ({capacity})"
+            ),
+            ConfigError::SoftCapacityExceedsCapacity {
+                soft_capacity,
+                capacity,
+            } => write!(
+                f,
+                "soft capacity ({soft_capacity}) exceeds capacity ({capacity})"
+            ),
+            ConfigError::MinIdleExceedsCapacity { min_idle, capacity } => {
+                write!(f, "min idle ({min_idle}) exceeds capacity ({capacity})")
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Error returned by a fallible factory, see [`Builder::try_factory`](crate::Builder::try_factory).
+///
+/// The original error can be recovered with [`FactoryError::downcast_ref`].
+/// Two `FactoryError`s are equal only if they are clones of each other.
+#[derive(Debug, Clone)]
+pub struct FactoryError(Arc<dyn Error + Send + Sync>);
+
+impl FactoryError {
+    pub(crate) fn new<E>(err: E) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        Self(Arc::new(err))
+    }
+
+    /// Get a reference to the error returned by the factory.
+    pub fn get_ref(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.0
+    }
+
+    /// Get a reference to the error returned by the factory if it is of type `E`.
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.0.downcast_ref()
+    }
+}
+
+impl PartialEq for FactoryError {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for FactoryError {}
+
+impl Display for FactoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for FactoryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{Entry, Pool, PoolApi, PullError};
+
+/// A pool falling back to a secondary pool when the primary one is exhausted,
+/// e.g. a small low-latency pool backed by a big overflow pool.
+///
+/// Created by [`Pool::with_fallback`]. Each item is recycled back into the pool
+/// it was pulled from.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::Pool;
+///
+/// let primary: Pool<u32> = Pool::with_capacity(1);
+/// let pool = primary.with_fallback(Pool::with_capacity(4));
+/// let item1 = pool.pull().unwrap();
+/// let item2 = pool.pull().unwrap();
+/// assert_eq!(pool.primary().in_use(), 1);
+/// assert_eq!(pool.secondary().in_use(), 1);
+/// drop(item2);
+/// assert_eq!(pool.secondary().in_use(), 0);
+/// ```
+#[derive(Debug)]
+pub struct FallbackPool<T> {
+    primary: Pool<T>,
+    secondary: Pool<T>,
+}
+
+impl<T> FallbackPool<T> {
+    pub(crate) fn new(primary: Pool<T>, secondary: Pool<T>) -> Self {
+        Self { primary, secondary }
+    }
+
+    /// Pull an item from the primary pool, or from the secondary pool if the
+    /// primary one is exhausted. Only the secondary pool may block, according
+    /// to its exhausted policy.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull(&self) -> Result<Entry<'_, T>, PullError> {
+        match self.primary.try_pull() {
+            Err(PullError::Exhausted) => self.secondary.pull(),
+            result => result,
+        }
+    }
+
+    /// Get the primary pool.
+    pub fn primary(&self) -> &Pool<T> {
+        &self.primary
+    }
+
+    /// Get the secondary pool.
+    pub fn secondary(&self) -> &Pool<T> {
+        &self.secondary
+    }
+
+    /// Split the composite back into the primary and secondary pools.
+    pub fn into_inner(self) -> (Pool<T>, Pool<T>) {
+        (self.primary, self.secondary)
+    }
+}
+
+impl<T> PoolApi<T> for FallbackPool<T> {
+    type Entry<'a>
+        = Entry<'a, T>
+    where
+        T: 'a;
+
+    fn pull(&self) -> Result<Self::Entry<'_>, PullError> {
+        FallbackPool::pull(self)
+    }
+
+    fn capacity(&self) -> usize {
+        self.primary.capacity() + self.secondary.capacity()
+    }
+
+    fn in_use(&self) -> usize {
+        self.primary.in_use() + self.secondary.in_use()
+    }
+}
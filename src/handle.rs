@@ -0,0 +1,70 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::Pool;
+
+/// A shared handle to a [`Pool`].
+///
+/// `PoolHandle` owns an `Arc` of the pool, so owned pulls such as
+/// [`Pool::pull_owned`] can be called directly on the handle. Cloning the
+/// handle is cheap and refers to the same pool.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Config, Pool};
+///
+/// let mut config = Config::default();
+/// config.capacity = 2;
+/// let pool = Pool::<u32>::shared(config);
+/// let clone_pool = pool.clone();
+/// let handle = std::thread::spawn(move || clone_pool.pull_owned_with(|x| *x = 42).unwrap());
+/// let item = handle.join().unwrap();
+/// assert_eq!(*item, 42);
+/// assert_eq!(pool.available(), 1);
+/// ```
+#[derive(Debug)]
+pub struct PoolHandle<T> {
+    pool: Arc<Pool<T>>,
+}
+
+impl<T> PoolHandle<T> {
+    /// Get the inner `Arc` of the pool.
+    pub fn as_arc(&self) -> &Arc<Pool<T>> {
+        &self.pool
+    }
+
+    /// Consume the handle and return the inner `Arc` of the pool.
+    pub fn into_arc(self) -> Arc<Pool<T>> {
+        self.pool
+    }
+}
+
+impl<T> Clone for PoolHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<T> Deref for PoolHandle<T> {
+    type Target = Arc<Pool<T>>;
+    fn deref(&self) -> &Self::Target {
+        &self.pool
+    }
+}
+
+impl<T> From<Pool<T>> for PoolHandle<T> {
+    fn from(pool: Pool<T>) -> Self {
+        Self {
+            pool: Arc::new(pool),
+        }
+    }
+}
+
+impl<T> From<Arc<Pool<T>>> for PoolHandle<T> {
+    fn from(pool: Arc<Pool<T>>) -> Self {
+        Self { pool }
+    }
+}
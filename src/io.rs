@@ -0,0 +1,59 @@
+use std::io::{self, Read, Write};
+
+use crate::{Entry, OwnedEntry, UniqueEntry};
+
+fn shared_error() -> io::Error {
+    io::Error::other("can not mutably access a shared entry")
+}
+
+impl<'a, T: Read> Read for UniqueEntry<'a, T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.get_mut().read(buf)
+    }
+}
+
+impl<'a, T: Write> Write for UniqueEntry<'a, T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.get_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.get_mut().flush()
+    }
+}
+
+/// Reading fails if other references to the inner item exist.
+impl<'a, T: Read> Read for Entry<'a, T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.get_mut().ok_or_else(shared_error)?.read(buf)
+    }
+}
+
+/// Writing fails if other references to the inner item exist.
+impl<'a, T: Write> Write for Entry<'a, T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.get_mut().ok_or_else(shared_error)?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.get_mut().ok_or_else(shared_error)?.flush()
+    }
+}
+
+/// Reading fails if other references to the inner item exist.
+impl<T: Read> Read for OwnedEntry<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.get_mut().ok_or_else(shared_error)?.read(buf)
+    }
+}
+
+/// Writing fails if other references to the inner item exist.
+impl<T: Write> Write for OwnedEntry<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.get_mut().ok_or_else(shared_error)?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.get_mut().ok_or_else(shared_error)?.flush()
+    }
+}
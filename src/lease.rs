@@ -0,0 +1,117 @@
+use std::fmt::{self, Debug, Display};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering::*;
+use std::time::Instant;
+
+use crate::Entry;
+
+/// The leased item is still held.
+const ACTIVE: u8 = 0;
+/// The leased item has been returned before its deadline.
+const RETURNED: u8 = 1;
+/// The lease has expired and the pool no longer counts the item.
+const REVOKED: u8 = 2;
+
+/// State of a lease, shared by the leased entry and the pool.
+#[derive(Debug)]
+pub(crate) struct Lease {
+    deadline: Instant,
+    state: AtomicU8,
+}
+
+impl Lease {
+    pub(crate) fn new(deadline: Instant) -> Self {
+        Self {
+            deadline,
+            state: AtomicU8::new(ACTIVE),
+        }
+    }
+
+    pub(crate) fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.state.load(Acquire) == ACTIVE
+    }
+
+    /// Revoke the lease if it is still active. Return whether it has been
+    /// revoked now.
+    pub(crate) fn revoke(&self) -> bool {
+        self.state
+            .compare_exchange(ACTIVE, REVOKED, AcqRel, Acquire)
+            .is_ok()
+    }
+
+    /// End the lease as the item is returned. Return `false` if it has been
+    /// revoked before.
+    fn finish(&self) -> bool {
+        self.state
+            .compare_exchange(ACTIVE, RETURNED, AcqRel, Acquire)
+            .is_ok()
+    }
+}
+
+/// An item leased from the pool until a deadline, see [`Pool::pull_leased`].
+///
+/// Once the lease is revoked, the item no longer counts as allocated by the
+/// pool, and it is destroyed instead of being recycled when the entry is
+/// dropped.
+///
+/// [`Pool::pull_leased`]: crate::Pool::pull_leased
+pub struct LeasedEntry<'a, T> {
+    entry: Entry<'a, T>,
+    lease: std::sync::Arc<Lease>,
+}
+
+impl<'a, T> LeasedEntry<'a, T> {
+    pub(crate) fn new(entry: Entry<'a, T>, lease: std::sync::Arc<Lease>) -> Self {
+        Self { entry, lease }
+    }
+
+    /// Get the deadline of the lease.
+    pub fn deadline(&self) -> Instant {
+        self.lease.deadline
+    }
+
+    /// Check whether the lease has been revoked by the pool.
+    pub fn is_revoked(&self) -> bool {
+        self.lease.state.load(Acquire) == REVOKED
+    }
+}
+
+impl<T> Drop for LeasedEntry<'_, T> {
+    fn drop(&mut self) {
+        if self.lease.finish() {
+            self.entry.pool.finish_lease();
+        } else if let Some(item) = self.entry.item.take() {
+            self.entry.pool.drop_revoked(item);
+        }
+    }
+}
+
+impl<T> Deref for LeasedEntry<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.entry
+    }
+}
+
+impl<T> DerefMut for LeasedEntry<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.entry
+    }
+}
+
+impl<T: Debug> Debug for LeasedEntry<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Display> Display for LeasedEntry<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
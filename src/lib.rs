@@ -5,7 +5,7 @@
 //! - Configurable capacity and preallocation.
 //! - Thread-safe: Multiple threads can pull and recycle items concurrently.
 //! - Automatic reclamation of unused item when the continuous occurrence
-//! of `surplus-pull` reaches a certain threshold if `auto_reclaim` is enabled.
+//!   of `surplus-pull` reaches a certain threshold if `auto_reclaim` is enabled.
 //!
 //! # `surplus-pull`
 //!
@@ -69,10 +69,57 @@
 //! receiver.join().unwrap();
 //! ```
 
+mod api;
+mod backoff;
+#[cfg(feature = "bytes")]
+mod buf;
 mod builder;
+mod cached;
 mod entry;
+mod erased;
+mod error;
+mod fallback;
+mod handle;
+mod io;
+mod lease;
+mod map;
+mod mapped;
+mod notify;
 mod pool;
+#[cfg(feature = "memory-pressure")]
+pub mod pressure;
+mod queue;
+mod registry;
+mod reservation;
+mod staged;
+mod static_pool;
+#[cfg(feature = "stream")]
+mod stream;
+mod token;
 
+pub use api::PoolApi;
+pub use backoff::BackoffStrategy;
 pub use builder::Builder;
-pub use entry::{Entry, OwnedEntry};
-pub use pool::{Config, Pool};
+pub use cached::{CachedEntry, CachedPool};
+pub use entry::{Entry, OwnedEntry, OwnedWeakEntry, UniqueEntry, WeakEntry};
+pub use erased::ErasedEntry;
+pub use error::{ConfigError, FactoryError, PullError};
+pub use fallback::FallbackPool;
+pub use handle::PoolHandle;
+pub use lease::LeasedEntry;
+pub use map::PoolMap;
+pub use mapped::{MappedEntry, MappedMutEntry, OwnedMappedEntry};
+pub use notify::{Interrupter, Notified};
+pub use pool::{
+    ClearFn, ClearPolicy, Config, ConfigView, ExhaustedPolicy, Pool, PreallocMode, PullKind,
+    QuarantineWindow, RecycleDecision,
+};
+#[cfg(feature = "instrument")]
+pub use pool::{HoldWarningFn, PullRecord};
+pub use registry::{global, register_global};
+pub use reservation::Reservation;
+pub use staged::{StagedBuilder, stage};
+pub use static_pool::{StaticEntry, StaticPool};
+#[cfg(feature = "stream")]
+pub use stream::EntriesStream;
+pub use token::PoolToken;
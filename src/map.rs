@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+use std::sync::{Arc, RwLock};
+
+use crate::{Config, OwnedEntry, Pool, PoolHandle, PullError};
+
+/// A map of pools created lazily, one per key, e.g. per size class of buffers
+/// or per downstream host.
+///
+/// All the pools are created from the same configuration template.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Config, PoolMap};
+///
+/// let mut config = Config::default();
+/// config.capacity = 4;
+/// let map = PoolMap::<&str, Vec<u8>>::new(config);
+/// let item = map.pull_owned(&"db").unwrap();
+/// let other = map.pull_owned(&"cache").unwrap();
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map.in_use(), 2);
+/// drop(item);
+/// assert_eq!(map.evict_idle(), 1);
+/// assert_eq!(map.len(), 1);
+/// ```
+pub struct PoolMap<K, T> {
+    /// Configuration template of the pools.
+    config: Config<T>,
+    pools: RwLock<HashMap<K, PoolHandle<T>>>,
+}
+
+impl<K, T> PoolMap<K, T>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Create an empty map whose pools are created with `config`.
+    pub fn new(config: Config<T>) -> Self {
+        Self {
+            config,
+            pools: RwLock::default(),
+        }
+    }
+
+    /// Get the pool of `key`, creating it if it does not exist yet.
+    pub fn pool(&self, key: &K) -> PoolHandle<T> {
+        if let Some(pool) = self.read().get(key) {
+            return pool.clone();
+        }
+        self.write()
+            .entry(key.clone())
+            .or_insert_with(|| PoolHandle::from(Pool::with_config(self.config.clone())))
+            .clone()
+    }
+
+    /// Pull an owned item from the pool of `key`, creating the pool if it
+    /// does not exist yet.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned(&self, key: &K) -> Result<OwnedEntry<T>, PullError> {
+        self.pool(key).pull_owned()
+    }
+
+    /// Remove the pools which are not used, i.e. without any item in use nor
+    /// any handle outside the map. Return the number of removed pools.
+    pub fn evict_idle(&self) -> usize {
+        let mut pools = self.write();
+        let len = pools.len();
+        pools.retain(|_, pool| Arc::strong_count(pool.as_arc()) > 1);
+        len - pools.len()
+    }
+
+    /// Get the number of pools.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Check whether the map has no pool.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Get the number of items allocated by all the pools.
+    pub fn allocated(&self) -> usize {
+        self.read().values().map(|pool| pool.allocated()).sum()
+    }
+
+    /// Get the number of items in use in all the pools.
+    pub fn in_use(&self) -> usize {
+        self.read().values().map(|pool| pool.in_use()).sum()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<K, PoolHandle<T>>> {
+        self.pools.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<K, PoolHandle<T>>> {
+        self.pools.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<K: Debug, T> Debug for PoolMap<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pools = self.pools.read().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("PoolMap")
+            .field("config", &self.config)
+            .field("keys", &pools.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
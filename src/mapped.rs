@@ -0,0 +1,354 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+use crate::{Entry, OwnedEntry, UniqueEntry};
+
+/// A projection of an [`Entry`] into a part of its item, e.g. a field.
+///
+/// Created by [`Entry::map`]. `MappedEntry` keeps the item checked out while it
+/// is alive, but only exposes the projected reference. When the last reference
+/// is dropped, the item is returned to the pool.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::Pool;
+///
+/// #[derive(Default)]
+/// struct Message {
+///     header: u32,
+///     body: Vec<u8>,
+/// }
+///
+/// let pool: Pool<Message> = Pool::with_capacity(1);
+/// let item = pool.pull_with(|m| m.body.extend_from_slice(b"hello")).unwrap();
+/// let body = item.map(|m| m.body.as_slice());
+/// assert_eq!(&*body, b"hello");
+/// assert_eq!(pool.in_use(), 1);
+/// drop(body);
+/// assert_eq!(pool.in_use(), 0);
+/// ```
+pub struct MappedEntry<'a, T, U: ?Sized> {
+    entry: Entry<'a, T>,
+    // Points into the item held by `entry`, which is never moved or mutated
+    // while it is shared.
+    value: NonNull<U>,
+}
+
+unsafe impl<'a, T: Send + Sync, U: ?Sized + Sync> Send for MappedEntry<'a, T, U> {}
+unsafe impl<'a, T: Send + Sync, U: ?Sized + Sync> Sync for MappedEntry<'a, T, U> {}
+
+impl<'a, T, U: ?Sized> MappedEntry<'a, T, U> {
+    pub(crate) fn new<F>(entry: Entry<'a, T>, f: F) -> Self
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let value = NonNull::from(f(entry.get()));
+        Self { entry, value }
+    }
+
+    /// Project the entry further into a part of the mapped value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<(u32, String)> = Pool::with_capacity(1);
+    /// let item = pool.pull_with(|t| t.1.push_str("hello")).unwrap();
+    /// let sub = item.map(|t| &t.1).map(|s| &s[1..3]);
+    /// assert_eq!(&*sub, "el");
+    /// ```
+    pub fn map<V, F>(self, f: F) -> MappedEntry<'a, T, V>
+    where
+        V: ?Sized,
+        F: FnOnce(&U) -> &V,
+    {
+        let value = NonNull::from(f(unsafe { self.value.as_ref() }));
+        MappedEntry {
+            entry: self.entry,
+            value,
+        }
+    }
+
+    /// Get the entry of the whole item.
+    pub fn entry(&self) -> &Entry<'a, T> {
+        &self.entry
+    }
+
+    /// Convert back into the entry of the whole item.
+    pub fn into_entry(self) -> Entry<'a, T> {
+        self.entry
+    }
+}
+
+impl<'a, T, U: ?Sized> Clone for MappedEntry<'a, T, U> {
+    /// Makes a clone of the `MappedEntry` that points to the same allocation.
+    fn clone(&self) -> Self {
+        Self {
+            entry: self.entry.clone(),
+            value: self.value,
+        }
+    }
+}
+
+impl<'a, T, U: ?Sized + PartialEq> PartialEq for MappedEntry<'a, T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl<'a, T, U: ?Sized + Eq> Eq for MappedEntry<'a, T, U> {}
+
+impl<'a, T, U: ?Sized + PartialOrd> PartialOrd for MappedEntry<'a, T, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<'a, T, U: ?Sized + Ord> Ord for MappedEntry<'a, T, U> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<'a, T, U: ?Sized + Hash> Hash for MappedEntry<'a, T, U> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T, U: ?Sized + serde::Serialize> serde::Serialize for MappedEntry<'a, T, U> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "stable_deref")]
+unsafe impl<'a, T, U: ?Sized> stable_deref_trait::StableDeref for MappedEntry<'a, T, U> {}
+#[cfg(feature = "stable_deref")]
+unsafe impl<'a, T, U: ?Sized> stable_deref_trait::CloneStableDeref for MappedEntry<'a, T, U> {}
+
+impl<'a, T, U: ?Sized> Deref for MappedEntry<'a, T, U> {
+    type Target = U;
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<'a, T, U: ?Sized + Debug> Debug for MappedEntry<'a, T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<'a, T, U: ?Sized + Display> Display for MappedEntry<'a, T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// A projection of an [`OwnedEntry`] into a part of its item, e.g. a field.
+///
+/// Created by [`OwnedEntry::map_owned`]. Like [`MappedEntry`], but holds an
+/// `Arc` reference to the pool, so it can be sent across threads and stored in
+/// futures.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::Pool;
+/// use std::sync::Arc;
+///
+/// let pool: Arc<Pool<(u32, String)>> = Arc::new(Pool::with_capacity(1));
+/// let item = pool.pull_owned_with(|t| t.1.push_str("hello")).unwrap();
+/// let name = item.map_owned(|t| t.1.as_str());
+/// std::thread::spawn(move || assert_eq!(&*name, "hello"))
+///     .join()
+///     .unwrap();
+/// assert_eq!(pool.available(), 1);
+/// ```
+pub struct OwnedMappedEntry<T, U: ?Sized> {
+    entry: OwnedEntry<T>,
+    // Points into the item held by `entry`, which is never moved or mutated
+    // while it is shared.
+    value: NonNull<U>,
+}
+
+unsafe impl<T: Send + Sync, U: ?Sized + Sync> Send for OwnedMappedEntry<T, U> {}
+unsafe impl<T: Send + Sync, U: ?Sized + Sync> Sync for OwnedMappedEntry<T, U> {}
+
+impl<T, U: ?Sized> OwnedMappedEntry<T, U> {
+    pub(crate) fn new<F>(entry: OwnedEntry<T>, f: F) -> Self
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let value = NonNull::from(f(entry.get()));
+        Self { entry, value }
+    }
+
+    /// Project the entry further into a part of the mapped value.
+    pub fn map_owned<V, F>(self, f: F) -> OwnedMappedEntry<T, V>
+    where
+        V: ?Sized,
+        F: FnOnce(&U) -> &V,
+    {
+        let value = NonNull::from(f(unsafe { self.value.as_ref() }));
+        OwnedMappedEntry {
+            entry: self.entry,
+            value,
+        }
+    }
+
+    /// Get the entry of the whole item.
+    pub fn entry(&self) -> &OwnedEntry<T> {
+        &self.entry
+    }
+
+    /// Convert back into the entry of the whole item.
+    pub fn into_entry(self) -> OwnedEntry<T> {
+        self.entry
+    }
+}
+
+impl<T, U: ?Sized> Clone for OwnedMappedEntry<T, U> {
+    /// Makes a clone of the `OwnedMappedEntry` that points to the same allocation.
+    fn clone(&self) -> Self {
+        Self {
+            entry: self.entry.clone(),
+            value: self.value,
+        }
+    }
+}
+
+impl<T, U: ?Sized + PartialEq> PartialEq for OwnedMappedEntry<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl<T, U: ?Sized + Eq> Eq for OwnedMappedEntry<T, U> {}
+
+impl<T, U: ?Sized + PartialOrd> PartialOrd for OwnedMappedEntry<T, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T, U: ?Sized + Ord> Ord for OwnedMappedEntry<T, U> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T, U: ?Sized + Hash> Hash for OwnedMappedEntry<T, U> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+#[cfg(feature = "stable_deref")]
+unsafe impl<T, U: ?Sized> stable_deref_trait::StableDeref for OwnedMappedEntry<T, U> {}
+#[cfg(feature = "stable_deref")]
+unsafe impl<T, U: ?Sized> stable_deref_trait::CloneStableDeref for OwnedMappedEntry<T, U> {}
+
+impl<T, U: ?Sized> Deref for OwnedMappedEntry<T, U> {
+    type Target = U;
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T, U: ?Sized + Debug> Debug for OwnedMappedEntry<T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T, U: ?Sized + Display> Display for OwnedMappedEntry<T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, U: ?Sized + serde::Serialize> serde::Serialize for OwnedMappedEntry<T, U> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+/// A mutable projection of a [`UniqueEntry`] into a part of its item.
+///
+/// Created by [`UniqueEntry::split_mut`], which hands out two of them over
+/// disjoint parts of the same item. Each keeps the item checked out while it
+/// is alive; when both are dropped, the item is returned to the pool.
+pub struct MappedMutEntry<'a, T, U: ?Sized> {
+    // Only kept to hold the item checked out, the item itself is never
+    // accessed through it.
+    _entry: Entry<'a, T>,
+    // Points into the item held by `_entry`, disjoint from the value of the
+    // other half of the split.
+    value: NonNull<U>,
+}
+
+unsafe impl<'a, T: Send + Sync, U: ?Sized + Send> Send for MappedMutEntry<'a, T, U> {}
+unsafe impl<'a, T: Send + Sync, U: ?Sized + Sync> Sync for MappedMutEntry<'a, T, U> {}
+
+impl<'a, T, U: ?Sized> MappedMutEntry<'a, T, U> {
+    pub(crate) fn split<V, F>(
+        mut entry: UniqueEntry<'a, T>,
+        f: F,
+    ) -> (Self, MappedMutEntry<'a, T, V>)
+    where
+        V: ?Sized,
+        F: FnOnce(&mut T) -> (&mut U, &mut V),
+    {
+        let (left, right) = f(entry.get_mut());
+        let (left, right) = (NonNull::from(left), NonNull::from(right));
+        let entry = entry.into_shared();
+        let left = Self {
+            _entry: entry.clone(),
+            value: left,
+        };
+        let right = MappedMutEntry {
+            _entry: entry,
+            value: right,
+        };
+        (left, right)
+    }
+}
+
+impl<'a, T, U: ?Sized> Deref for MappedMutEntry<'a, T, U> {
+    type Target = U;
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<'a, T, U: ?Sized> DerefMut for MappedMutEntry<'a, T, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.value.as_mut() }
+    }
+}
+
+impl<'a, T, U: ?Sized + Debug> Debug for MappedMutEntry<'a, T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<'a, T, U: ?Sized + Display> Display for MappedMutEntry<'a, T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
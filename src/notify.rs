@@ -0,0 +1,164 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::Ordering::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, fence};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+
+use crate::Pool;
+
+/// Wait list used to wake up tasks and threads waiting for an item to be recycled.
+///
+/// Recycling an item only takes the lock when there are registered waiters, so
+/// the fast path stays lock-free.
+#[derive(Debug, Default)]
+pub(crate) struct Notify {
+    /// Number of registered wakers and blocked threads.
+    waiters: AtomicUsize,
+    /// Registered wakers and blocked threads.
+    state: Mutex<Waiters>,
+    /// Condition variable for blocked threads.
+    condvar: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct Waiters {
+    /// Registered wakers.
+    wakers: Vec<Waker>,
+    /// Number of blocked threads.
+    blocked: usize,
+}
+
+impl Waiters {
+    fn len(&self) -> usize {
+        self.wakers.len() + self.blocked
+    }
+}
+
+impl Notify {
+    fn lock_state(&self) -> MutexGuard<'_, Waiters> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Register a waker to be woken on the next notification.
+    ///
+    /// The caller must check the pool again after registering, otherwise a
+    /// notification sent in between may be missed.
+    pub(crate) fn register(&self, waker: &Waker) {
+        let mut state = self.lock_state();
+        if !state.wakers.iter().any(|w| w.will_wake(waker)) {
+            state.wakers.push(waker.clone());
+        }
+        self.waiters.store(state.len(), SeqCst);
+        fence(SeqCst);
+    }
+
+    /// Block the current thread until `ready` returns `true`, re-checking it on
+    /// each notification. Return `false` if the deadline is reached first.
+    pub(crate) fn wait_until<F>(&self, deadline: Option<Instant>, ready: F) -> bool
+    where
+        F: Fn() -> bool,
+    {
+        let mut state = self.lock_state();
+        state.blocked += 1;
+        self.waiters.store(state.len(), SeqCst);
+        fence(SeqCst);
+        let result = loop {
+            if ready() {
+                break true;
+            }
+            match deadline {
+                None => state = self.condvar.wait(state).unwrap_or_else(|e| e.into_inner()),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break false;
+                    }
+                    state = self
+                        .condvar
+                        .wait_timeout(state, deadline - now)
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0;
+                }
+            }
+        };
+        state.blocked -= 1;
+        self.waiters.store(state.len(), SeqCst);
+        result
+    }
+
+    /// Wake all registered waiters.
+    pub(crate) fn notify_waiters(&self) {
+        fence(SeqCst);
+        if self.waiters.load(SeqCst) == 0 {
+            return;
+        }
+        let wakers = {
+            let mut state = self.lock_state();
+            let wakers = std::mem::take(&mut state.wakers);
+            self.waiters.store(state.len(), SeqCst);
+            if state.blocked > 0 {
+                self.condvar.notify_all();
+            }
+            wakers
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// Handle to interrupt blocking pulls of a pool.
+///
+/// Created by [`Pool::interrupter`]. Once interrupted, pulls made through
+/// [`Pool::pull_interruptible`] with this interrupter, or any of its clones,
+/// fail with [`PullError::Interrupted`](crate::PullError::Interrupted) instead
+/// of blocking, until the interrupter is reset.
+#[derive(Debug, Clone)]
+pub struct Interrupter {
+    interrupted: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl Interrupter {
+    pub(crate) fn new(notify: Arc<Notify>) -> Self {
+        Self {
+            interrupted: Arc::new(AtomicBool::new(false)),
+            notify,
+        }
+    }
+
+    /// Interrupt all pulls blocked on this interrupter.
+    pub fn interrupt(&self) {
+        self.interrupted.store(true, SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    /// Check whether the interrupter has been interrupted.
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted.load(SeqCst)
+    }
+
+    /// Reset the interrupter so that pulls block again.
+    pub fn reset(&self) {
+        self.interrupted.store(false, SeqCst);
+    }
+}
+
+/// Future that completes when the pool has available items.
+///
+/// Created by [`Pool::notified`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Notified<'a, T> {
+    pub(crate) pool: &'a Pool<T>,
+}
+
+impl<'a, T> Future for Notified<'a, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.pool.poll_available(cx)
+    }
+}
@@ -1,12 +1,27 @@
-use std::cmp::max;
-use std::sync::Arc;
+use std::any::Any;
+use std::cmp::{max, min};
+#[cfg(feature = "instrument")]
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+use std::pin::Pin;
 use std::sync::atomic::Ordering::*;
-use std::sync::atomic::{AtomicBool, AtomicUsize};
+use std::sync::atomic::{AtomicBool, AtomicUsize, fence};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
-use crossbeam_queue::ArrayQueue;
-
-use crate::entry::Prc;
-use crate::{Entry, OwnedEntry};
+use crate::BackoffStrategy;
+use crate::entry::{Metadata, Prc};
+use crate::lease::Lease;
+use crate::notify::{Interrupter, Notified, Notify};
+use crate::queue::IdleQueue;
+use crate::token::TokenSlots;
+use crate::{
+    Builder, ConfigError, Entry, FactoryError, FallbackPool, LeasedEntry, OwnedEntry, PoolHandle,
+    PoolToken, PullError, Reservation, UniqueEntry,
+};
 
 /// A concurrent object pool.
 ///
@@ -51,28 +66,135 @@ use crate::{Entry, OwnedEntry};
 /// receiver.join().unwrap();
 /// ```
 #[derive(Debug)]
-pub struct Pool<T: Default> {
+pub struct Pool<T> {
     /// Configuration of the pool.
     config: Config<T>,
+    /// Reclamation settings, which can be changed while the pool is in use.
+    reclaim: ReclaimSettings,
     /// Inner queue holding the pooled items.
-    queue: ArrayQueue<Prc<T>>,
+    queue: IdleQueue<T>,
     /// Number of items currently allocated.
     allocated: AtomicUsize,
     /// Number of currently continues `surplus-pull` times
     surpluspulls: AtomicUsize,
     /// Whether an additional item has been allocated beyond the preallocated items.
     additional_allocated: AtomicBool,
+    /// Waiters to wake up when an item is recycled.
+    notify: Arc<Notify>,
+    /// Items converted into tokens by `OwnedEntry::into_raw`.
+    tokens: Mutex<TokenSlots<T>>,
+    /// Whether the pool has been closed, rejecting new pulls.
+    closed: AtomicBool,
+    /// Items set aside by the recycle hook.
+    quarantined: Mutex<Quarantined<T>>,
+    /// Recycled items held back by the quarantine window.
+    held_back: Mutex<HeldBack<T>>,
+    /// Leases of the items pulled with `pull_leased` which may still be active.
+    leases: Mutex<Vec<Arc<Lease>>>,
+    /// Number of active leases, to skip locking `leases` when there is none.
+    active_leases: AtomicUsize,
+    /// Where and when the items in use were pulled, keyed by item address,
+    /// and whether they have been reported as held for too long.
+    #[cfg(feature = "instrument")]
+    outstanding: Mutex<HashMap<usize, (PullRecord, bool)>>,
 }
 
-impl<T: Default> Drop for Pool<T> {
+impl<T> Drop for Pool<T> {
     fn drop(&mut self) {
         while let Some(item) = self.queue.pop() {
             unsafe { item.drop_slow() };
         }
+        let quarantined = self
+            .quarantined
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner());
+        for item in quarantined.0.drain(..) {
+            unsafe { item.drop_slow() };
+        }
+        let held_back = self.held_back.get_mut().unwrap_or_else(|e| e.into_inner());
+        for (item, _, _) in held_back.items.drain(..) {
+            unsafe { item.drop_slow() };
+        }
+        let tokens = self.tokens.get_mut().unwrap_or_else(|e| e.into_inner());
+        for item in tokens.drain() {
+            if item.dec_ref() == 1 {
+                fence(Acquire);
+                unsafe { item.drop_slow() };
+            }
+        }
+    }
+}
+
+/// Items set aside by the recycle hook of a pool.
+///
+/// They have no references outside the pool, so moving them between threads
+/// only requires `T: Send`, as for idle items.
+#[derive(Debug)]
+struct Quarantined<T>(Vec<Prc<T>>);
+
+unsafe impl<T: Send> Send for Quarantined<T> {}
+
+impl<T> Default for Quarantined<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+/// Recycled items held back by the quarantine window of a pool, in recycle
+/// order, with the pull count and time at which they were recycled.
+///
+/// They have no references outside the pool, so moving them between threads
+/// only requires `T: Send`, as for idle items.
+#[derive(Debug)]
+struct HeldBack<T> {
+    items: VecDeque<(Prc<T>, usize, Instant)>,
+    /// Number of pulls since the pool was created.
+    pulls: usize,
+}
+
+unsafe impl<T: Send> Send for HeldBack<T> {}
+
+impl<T> Default for HeldBack<T> {
+    fn default() -> Self {
+        Self {
+            items: VecDeque::new(),
+            pulls: 0,
+        }
+    }
+}
+
+/// Reclamation settings of a pool, initialized from its configuration.
+#[derive(Debug)]
+struct ReclaimSettings {
+    auto_reclaim: AtomicBool,
+    /// Whether reclamation is processed, which is never the case when all the
+    /// capacity is preallocated.
+    enabled: AtomicBool,
+    surpluspull_threshold: AtomicUsize,
+    idle_threshold: AtomicUsize,
+}
+
+impl ReclaimSettings {
+    fn new<T>(config: &Config<T>) -> Self {
+        Self {
+            auto_reclaim: AtomicBool::new(config.auto_reclaim),
+            enabled: AtomicBool::new(config.need_process_reclamation),
+            surpluspull_threshold: AtomicUsize::new(config.surpluspull_threshold_for_reclaim),
+            idle_threshold: AtomicUsize::new(config.idle_threshold_for_surpluspull),
+        }
+    }
+
+    fn store<T>(&self, config: &Config<T>) {
+        self.surpluspull_threshold
+            .store(config.surpluspull_threshold_for_reclaim, Relaxed);
+        self.idle_threshold
+            .store(config.idle_threshold_for_surpluspull, Relaxed);
+        self.auto_reclaim.store(config.auto_reclaim, Relaxed);
+        self.enabled.store(config.need_process_reclamation, Relaxed);
     }
 }
 
-impl<T: Default> Pool<T> {
+impl<T> Pool<T> {
     /// Create a new pool with the given preallocation and capacity.
     ///
     /// # Example
@@ -86,7 +208,10 @@ impl<T: Default> Pool<T> {
     /// let item = pool.pull().unwrap();
     /// assert_eq!(pool.available_noalloc(), 1);
     /// ```
-    pub fn new(prealloc: usize, capacity: usize) -> Self {
+    pub fn new(prealloc: usize, capacity: usize) -> Self
+    where
+        T: Default,
+    {
         Self::with_config(Config {
             capacity,
             prealloc,
@@ -107,7 +232,10 @@ impl<T: Default> Pool<T> {
     /// let item = pool.pull().unwrap();
     /// assert_eq!(pool.available(), 9);
     /// ```
-    pub fn with_capacity(capacity: usize) -> Self {
+    pub fn with_capacity(capacity: usize) -> Self
+    where
+        T: Default,
+    {
         Self::new(capacity, capacity)
     }
 
@@ -125,10 +253,44 @@ impl<T: Default> Pool<T> {
     /// assert_eq!(pool.available_noalloc(), 4);
     /// assert_eq!(pool.in_use(), 1);
     /// ```
-    pub fn with_capacity_half_prealloc(capacity: usize) -> Self {
+    pub fn with_capacity_half_prealloc(capacity: usize) -> Self
+    where
+        T: Default,
+    {
         Self::new(capacity / 2, capacity)
     }
 
+    /// Create a new pool with the given capacity, starting with the items of
+    /// `iter` idle in the pool, e.g. buffers restored from a snapshot. New items
+    /// are created with `T::default()` once the seeded items are all in use.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `iter` yields more items than `capacity`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool = Pool::from_iter([vec![1], vec![2]], 3);
+    /// assert_eq!(pool.available_noalloc(), 2);
+    /// let items: Vec<_> = (0..3).map(|_| pool.pull().unwrap()).collect();
+    /// assert_eq!(*items[0], [1]);
+    /// assert_eq!(*items[1], [2]);
+    /// assert!(items[2].is_empty());
+    /// ```
+    pub fn from_iter<I>(iter: I, capacity: usize) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Default,
+    {
+        Builder::new()
+            .capacity(capacity)
+            .seed_items(iter.into_iter().collect())
+            .build()
+    }
+
     /// Create a new pool with the given configuration.
     ///
     /// # Example
@@ -150,39 +312,87 @@ impl<T: Default> Pool<T> {
     /// let item2 = pool.pull().unwrap();
     /// assert_eq!(&*item2, "");
     /// ```
-    pub fn with_config(mut config: Config<T>) -> Self {
+    pub fn with_config(config: Config<T>) -> Self {
+        Self::with_config_and_seed(config, Vec::new())
+    }
+
+    /// Create a new pool with the given configuration, starting with the
+    /// `seed` items idle in the pool.
+    pub(crate) fn with_config_and_seed(mut config: Config<T>, seed: Vec<T>) -> Self {
+        // Seeded items count as preallocated.
+        config.prealloc = max(config.prealloc, seed.len());
         config.post_process();
         let prealloc = config.prealloc;
         assert!(
             prealloc <= config.capacity,
-            "prealloc must be less than or equal to capacity"
+            "prealloc must be less than or equal to capacity for pool `{}`",
+            config.display_name()
         );
 
         let queue_len = max(1, config.capacity);
+        let queue = IdleQueue::new(queue_len);
+        #[cfg(feature = "paranoid")]
+        let queue = queue.named(config.display_name());
         let pool = Self {
-            queue: ArrayQueue::new(queue_len),
+            queue,
             allocated: AtomicUsize::new(prealloc),
             surpluspulls: AtomicUsize::new(0),
             additional_allocated: AtomicBool::new(false),
+            notify: Arc::default(),
+            tokens: Mutex::default(),
+            closed: AtomicBool::new(false),
+            quarantined: Mutex::default(),
+            held_back: Mutex::default(),
+            leases: Mutex::default(),
+            active_leases: AtomicUsize::new(0),
+            #[cfg(feature = "instrument")]
+            outstanding: Mutex::default(),
+            reclaim: ReclaimSettings::new(&config),
             config,
         };
-        let mut items = Vec::with_capacity(prealloc);
-        for _ in 0..prealloc {
-            items.push(T::default());
-        }
-        while let Some(item) = items.pop() {
-            let _ = pool.queue.push(Prc::new_zero(item));
+        // Preallocation stops at the first failure of a fallible factory, the
+        // missing items are allocated on demand instead.
+        let missing = match pool.config.prealloc_mode {
+            PreallocMode::Eager => prealloc - seed.len(),
+            PreallocMode::Lazy { .. } => 0,
+            PreallocMode::Deferred => {
+                for _ in seed.len()..prealloc {
+                    let _ = pool.queue.push(Prc::new_uninit(pool.new_metadata()));
+                }
+                0
+            }
+        };
+        let mut items = seed;
+        items.extend((0..missing).map_while(|_| pool.new_value().ok()));
+        pool.allocated
+            .store(pool.queue.len() + items.len(), Relaxed);
+        for item in items {
+            let _ = pool.queue.push(Prc::new_zero(item, pool.new_metadata()));
         }
         pool
     }
 
-    /// Enable automatic reclamation of allocated items to reduce memory usage.
-    pub fn enable_auto_reclaim(&mut self) {
-        self.config.auto_reclaim = true;
-        self.config.post_process();
+    /// Create a new shared pool with the given configuration, returning a
+    /// [`PoolHandle`] which can be cloned and used for owned pulls.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Config, Pool};
+    ///
+    /// let pool = Pool::<u32>::shared(Config::default());
+    /// let item = pool.pull_owned().unwrap();
+    /// assert_eq!(pool.in_use(), 1);
+    /// ```
+    pub fn shared(config: Config<T>) -> PoolHandle<T> {
+        PoolHandle::from(Self::with_config(config))
     }
 
-    /// Get in used items count.
+    /// Free idle items until at most `target` items are allocated, e.g. at the
+    /// end of a batch job. Return the number of freed items.
+    ///
+    /// Items in use are never freed, so more than `target` items may still be
+    /// allocated afterwards.
     ///
     /// # Example
     ///
@@ -190,254 +400,2863 @@ impl<T: Default> Pool<T> {
     /// use concurrent_pool::Pool;
     ///
     /// let pool: Pool<u32> = Pool::with_capacity(10);
-    /// assert_eq!(pool.in_use(), 0);
     /// let item = pool.pull().unwrap();
-    /// assert_eq!(pool.in_use(), 1);
-    /// let item2 = pool.pull().unwrap();
-    /// assert_eq!(pool.in_use(), 2);
+    /// assert_eq!(pool.shrink_to(4), 6);
+    /// assert_eq!(pool.allocated(), 4);
+    /// assert_eq!(pool.shrink_to(0), 3);
+    /// assert_eq!(pool.allocated(), 1);
     /// ```
-    pub fn in_use(&self) -> usize {
-        self.allocated.load(Relaxed) - self.queue.len()
+    pub fn shrink_to(&self, target: usize) -> usize {
+        let mut freed = 0;
+        while self.allocated.load(Acquire) > target && self.free_idle_item() {
+            freed += 1;
+        }
+        freed
     }
 
-    /// Get allocated items count.
+    /// Remove all the idle items from the pool and return their values, e.g. to
+    /// hand warm buffers over to another pool. Idle slots whose value has not
+    /// been created yet are freed.
     ///
     /// # Example
     ///
     /// ```rust
     /// use concurrent_pool::Pool;
     ///
-    /// let pool = Pool::<usize>::new(2, 5);
-    /// assert_eq!(pool.allocated(), 2);
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(3);
+    /// let item = pool.pull().unwrap();
+    /// assert_eq!(pool.drain_idle().len(), 2);
+    /// assert_eq!(pool.allocated(), 1);
+    ///
+    /// let other: Pool<Vec<u8>> = Pool::with_capacity(1);
+    /// drop(item);
+    /// for value in pool.drain_idle() {
+    ///     drop(other.pull_from(value));
+    /// }
+    /// assert_eq!(other.allocated(), 1);
     /// ```
-    pub fn allocated(&self) -> usize {
-        self.allocated.load(Acquire)
+    pub fn drain_idle(&self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.queue.len());
+        while let Some(item) = self.queue.pop() {
+            if item.is_uninit() {
+                self.free(item);
+            } else {
+                values.push(self.detach(item));
+            }
+        }
+        if self.allocated.load(Acquire) <= self.config.prealloc {
+            self.additional_allocated.store(false, Relaxed);
+        }
+        values
     }
 
-    /// Get available items count.
+    /// Free the idle items for which `func` returns `false`, e.g. stale
+    /// connections or oversized buffers. Return the number of freed items.
+    ///
+    /// Idle slots whose value has not been created yet are kept. Items recycled
+    /// concurrently may be skipped.
     ///
     /// # Example
     ///
     /// ```rust
     /// use concurrent_pool::Pool;
     ///
-    /// let pool: Pool<u32> = Pool::with_capacity(10);
-    /// assert_eq!(pool.available(), 10);
-    /// let item = pool.pull().unwrap();
-    /// assert_eq!(pool.available(), 9);
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(2);
+    /// let mut item = pool.pull().unwrap();
+    /// item.reserve(4096);
+    /// drop(item);
+    /// assert_eq!(pool.retain_idle(|buf| buf.capacity() < 1024), 1);
+    /// assert_eq!(pool.allocated(), 1);
     /// ```
-    pub fn available(&self) -> usize {
-        self.config.capacity - self.in_use()
+    pub fn retain_idle<F>(&self, mut func: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut freed = 0;
+        for _ in 0..self.queue.len() {
+            let Some(item) = self.queue.pop() else {
+                break;
+            };
+            let keep = match item.is_uninit() {
+                true => true,
+                false => match catch_unwind(AssertUnwindSafe(|| func(&item))) {
+                    Ok(keep) => keep,
+                    // The item is put back before the panic is resumed.
+                    Err(payload) => {
+                        let _ = self.queue.push(item);
+                        resume_unwind(payload)
+                    }
+                },
+            };
+            if keep {
+                let _ = self.queue.push(item);
+            } else {
+                self.free(item);
+                freed += 1;
+            }
+        }
+        freed
     }
 
-    /// Get available items count without allocation.
+    /// Destroy all the idle items, including the ones held back or set aside,
+    /// and reset the counters of the pool as if it had just been built, e.g.
+    /// between benchmark iterations. Items in use are recycled as usual.
+    ///
+    /// Preallocated items are not created again, see [`Pool::reset`].
     ///
     /// # Example
     ///
     /// ```rust
     /// use concurrent_pool::Pool;
     ///
-    /// let pool: Pool<u32> = Pool::new(2, 5);
-    /// assert_eq!(pool.available_noalloc(), 2);
+    /// let pool: Pool<u32> = Pool::new(2, 4);
     /// let item = pool.pull().unwrap();
-    /// assert_eq!(pool.available_noalloc(), 1);
-    /// let item2 = pool.pull().unwrap();
-    /// assert_eq!(pool.available_noalloc(), 0);
-    /// let item3 = pool.pull().unwrap();
-    /// assert_eq!(pool.available_noalloc(), 0);
+    /// pool.clear();
+    /// assert_eq!(pool.allocated(), 1);
     /// drop(item);
     /// assert_eq!(pool.available_noalloc(), 1);
     /// ```
-    pub fn available_noalloc(&self) -> usize {
-        self.queue.len()
+    pub fn clear(&self) {
+        let held_back = std::mem::take(&mut *self.lock_held_back());
+        for (item, _, _) in held_back.items {
+            self.free(item);
+        }
+        let quarantined = std::mem::take(&mut self.lock_quarantined().0);
+        for item in quarantined {
+            self.free(item);
+        }
+        while let Some(item) = self.queue.pop() {
+            self.free(item);
+        }
+        self.surpluspulls.store(0, Relaxed);
+        self.additional_allocated
+            .store(self.allocated.load(Acquire) > self.config.prealloc, Relaxed);
+        self.notify.notify_waiters();
     }
 
-    /// Check if the pool is empty.
+    /// [Clear](Pool::clear) the pool, then preallocate items again according to
+    /// the configuration, counting the items in use as preallocated.
     ///
     /// # Example
     ///
     /// ```rust
     /// use concurrent_pool::Pool;
     ///
-    /// let pool: Pool<u32> = Pool::with_capacity(2);
-    /// assert!(!pool.is_empty());
-    /// let item1 = pool.pull().unwrap();
-    /// assert!(!pool.is_empty());
-    /// let item2 = pool.pull().unwrap();
-    /// assert!(pool.is_empty());
-    /// drop(item1);
-    /// assert!(!pool.is_empty());
+    /// let pool: Pool<u32> = Pool::new(2, 4);
+    /// let items = pool.pull_n(3).unwrap();
+    /// drop(items);
+    /// pool.reset();
+    /// assert_eq!(pool.allocated(), 2);
+    /// assert_eq!(pool.available_noalloc(), 2);
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.available() == 0
+    pub fn reset(&self) {
+        self.clear();
+        let missing = self
+            .config
+            .prealloc
+            .saturating_sub(self.allocated.load(Acquire));
+        match self.config.prealloc_mode {
+            PreallocMode::Eager => {
+                self.warm_up(missing);
+            }
+            // Lazy preallocation resumes with the next pull.
+            PreallocMode::Lazy { .. } => {}
+            PreallocMode::Deferred => {
+                for _ in 0..self.try_allocate(missing, false) {
+                    let _ = self.queue.push(Prc::new_uninit(self.new_metadata()));
+                }
+            }
+        }
+        self.additional_allocated
+            .store(self.allocated.load(Acquire) > self.config.prealloc, Relaxed);
     }
 
-    /// Get the capacity of the pool.
+    /// Free up to `n` idle items right away, e.g. from a memory watchdog,
+    /// starting with the least recently used ones. Return the number of freed
+    /// items.
     ///
     /// # Example
     ///
     /// ```rust
     /// use concurrent_pool::Pool;
     ///
-    /// let pool: Pool<u32> = Pool::with_capacity(10);
-    /// assert_eq!(pool.capacity(), 10);
+    /// let pool: Pool<u32> = Pool::with_capacity(4);
+    /// let item = pool.pull().unwrap();
+    /// assert_eq!(pool.reclaim_now(2), 2);
+    /// assert_eq!(pool.reclaim_now(5), 1);
+    /// assert_eq!(pool.allocated(), 1);
     /// ```
-    pub fn capacity(&self) -> usize {
-        self.config.capacity
+    pub fn reclaim_now(&self, n: usize) -> usize {
+        (0..n).take_while(|_| self.free_idle_item()).count()
     }
 
-    /// Pull an item from the pool. Return `None` if the pool is empty.
+    /// Free all the idle items beyond `min_idle`, including preallocated ones,
+    /// e.g. when the process is close to its memory limit. Return the number
+    /// of freed items.
+    ///
+    /// It is called by [`Pool::maintain`] when the function configured with
+    /// [`Builder::memory_pressure`](crate::Builder::memory_pressure) reports
+    /// memory pressure.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use concurrent_pool::Pool;
+    /// use concurrent_pool::Builder;
     ///
-    /// let pool: Pool<u32> = Pool::with_capacity(2);
-    /// let item1 = pool.pull().unwrap();
-    /// assert_eq!(*item1, 0);
+    /// let pool = Builder::<u32>::new().capacity(8).prealloc(4).min_idle(1).build();
+    /// let item = pool.pull().unwrap();
+    /// assert_eq!(pool.reclaim_on_pressure(), 2);
+    /// assert_eq!(pool.allocated(), 2);
     /// ```
-    pub fn pull(&self) -> Option<Entry<'_, T>> {
-        self.pull_inner().map(|item| Entry {
-            item: Some(item),
-            pool: self,
-        })
+    pub fn reclaim_on_pressure(&self) -> usize {
+        let mut freed = 0;
+        while self.queue.len() > self.config.min_idle && self.free_idle_item() {
+            freed += 1;
+        }
+        freed
+    }
+
+    /// Run the maintenance of the pool once: free the expired idle items,
+    /// reclaim surplus idle items if reclamation is enabled or if the process
+    /// is under memory pressure, and create idle items up to `min_idle`.
+    ///
+    /// These policies are otherwise only applied by pulls and recycles, so this
+    /// is meant to be called periodically while the pool may stay quiet, see
+    /// [`Builder::spawn_maintenance`](crate::Builder::spawn_maintenance).
+    pub fn maintain(&self) {
+        if self.is_closed() {
+            return;
+        }
+        self.evict_expired();
+        if self.config.memory_pressure.is_some_and(|func| func()) {
+            self.reclaim_on_pressure();
+        } else if self.reclaim.enabled.load(Relaxed) {
+            let high = self.config.idle_high_watermark;
+            if high > 0 {
+                if self.queue.len() >= high {
+                    self.reclaim_to(self.config.idle_low_watermark);
+                }
+            } else if self.additional_allocated.load(Relaxed)
+                && self.queue.len() >= self.reclaim.idle_threshold.load(Relaxed)
+            {
+                self.reclaim();
+            }
+        }
+        self.revoke_expired_leases();
+        self.replenish_idle();
+        #[cfg(feature = "instrument")]
+        self.report_held_too_long();
+    }
+
+    /// Revoke the expired leases, so the pool stops counting their items and
+    /// can allocate replacements. Return the number of revoked leases.
+    fn revoke_expired_leases(&self) -> usize {
+        if self.active_leases.load(Acquire) == 0 {
+            return 0;
+        }
+        let now = Instant::now();
+        let mut revoked = Vec::new();
+        self.lock_leases().retain(|lease| {
+            if lease.is_active() && lease.deadline() <= now && lease.revoke() {
+                revoked.push(lease.deadline());
+            }
+            lease.is_active()
+        });
+        for deadline in &revoked {
+            self.active_leases.fetch_sub(1, Release);
+            self.allocated.fetch_sub(1, Release);
+            self.notify.notify_waiters();
+            if let Some(func) = self.config.on_lease_revoked {
+                func(self.config.display_name(), *deadline);
+            }
+        }
+        revoked.len()
+    }
+
+    /// Get the deadline of the earliest active lease, which a blocked pull
+    /// waits for at most to revoke it.
+    fn next_lease_deadline(&self) -> Option<Instant> {
+        if self.active_leases.load(Acquire) == 0 {
+            return None;
+        }
+        self.lock_leases()
+            .iter()
+            .filter(|lease| lease.is_active())
+            .map(|lease| lease.deadline())
+            .min()
+    }
+
+    /// Account for a lease ended by returning its item before the deadline.
+    pub(crate) fn finish_lease(&self) {
+        self.active_leases.fetch_sub(1, Release);
+    }
+
+    /// Destroy an item whose lease has been revoked, its slot being already
+    /// freed.
+    pub(crate) fn drop_revoked(&self, item: Prc<T>) {
+        #[cfg(feature = "instrument")]
+        self.untrack(&item);
+        if item.dec_ref() == 1 {
+            fence(Acquire);
+            unsafe { item.drop_slow() };
+        }
+    }
+
+    fn lock_leases(&self) -> std::sync::MutexGuard<'_, Vec<Arc<Lease>>> {
+        self.leases.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Report the items held for longer than `max_hold_warning` to the
+    /// configured function, once per pull.
+    #[cfg(feature = "instrument")]
+    fn report_held_too_long(&self) {
+        let Some((threshold, func)) = self.config.max_hold_warning else {
+            return;
+        };
+        let held_too_long: Vec<_> = self
+            .lock_outstanding()
+            .values_mut()
+            .filter(|(record, warned)| !*warned && record.held_for() > threshold)
+            .map(|(record, warned)| {
+                *warned = true;
+                *record
+            })
+            .collect();
+        // The function is called without the lock, so it can use the pool.
+        for record in held_too_long {
+            func(self.config.display_name(), &record);
+        }
+    }
+
+    /// Free idle items until at most the preallocated number of items are
+    /// allocated. See [`Pool::shrink_to`].
+    pub fn shrink_to_prealloc(&self) -> usize {
+        self.shrink_to(self.config.prealloc)
+    }
+
+    /// Combine the pool with a secondary pool, pulled from when this one is
+    /// exhausted. See [`FallbackPool`].
+    pub fn with_fallback(self, other: Pool<T>) -> FallbackPool<T> {
+        FallbackPool::new(self, other)
+    }
+
+    /// Enable automatic reclamation of allocated items to reduce memory usage.
+    pub fn enable_auto_reclaim(&mut self) {
+        self.config.auto_reclaim = true;
+        self.config.post_process();
+        self.reclaim = ReclaimSettings::new(&self.config);
     }
 
-    /// Pull an item from the pool and apply a function to it. Return `None` if the pool is empty.
+    /// Change the reclamation settings while the pool is in use, to tune the
+    /// trade-off between memory usage and allocations to the observed load.
+    ///
+    /// A threshold of 0 is derived from the capacity, as when the pool is
+    /// built.
     ///
     /// # Example
     ///
     /// ```rust
     /// use concurrent_pool::Pool;
     ///
+    /// let pool: Pool<u32> = Pool::with_capacity(100);
+    /// pool.reconfigure_reclaim(4, 10, true).unwrap();
+    /// let config = pool.config();
+    /// assert!(config.auto_reclaim);
+    /// assert_eq!(config.surpluspull_threshold_for_reclaim, 4);
+    /// assert_eq!(config.idle_threshold_for_surpluspull, 10);
+    /// assert!(pool.reconfigure_reclaim(4, 101, true).is_err());
+    /// ```
+    pub fn reconfigure_reclaim(
+        &self,
+        surpluspull_threshold: usize,
+        idle_threshold: usize,
+        auto_reclaim: bool,
+    ) -> Result<(), ConfigError> {
+        let mut config = Config::<()>::default();
+        config.capacity = self.config.capacity;
+        config.prealloc = self.config.prealloc;
+        config.auto_reclaim = auto_reclaim;
+        config.surpluspull_threshold_for_reclaim = surpluspull_threshold;
+        config.idle_threshold_for_surpluspull = idle_threshold;
+        config.validate()?;
+        config.post_process();
+        self.reclaim.store(&config);
+        self.surpluspulls.store(0, Relaxed);
+        Ok(())
+    }
+
+    /// Close the pool during shutdown: subsequent pulls fail with
+    /// [`PullError::Closed`] and blocked or async waiters are woken up, while
+    /// outstanding entries can still be used and returned.
+    ///
+    /// Idle items are freed right away, and outstanding items are freed instead
+    /// of being recycled once their last entry is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Pool, PullError};
+    ///
     /// let pool: Pool<u32> = Pool::with_capacity(2);
-    /// let item1 = pool.pull_with(|x| *x = 42).unwrap();
-    /// assert_eq!(*item1, 42);
+    /// let item = pool.pull().unwrap();
+    /// pool.close();
+    /// assert!(pool.is_closed());
+    /// assert_eq!(pool.pull().unwrap_err(), PullError::Closed);
+    /// drop(item);
+    /// assert_eq!(pool.allocated(), 0);
     /// ```
-    pub fn pull_with<F>(&self, func: F) -> Option<Entry<'_, T>>
-    where
-        F: FnOnce(&mut T),
-    {
-        self.pull().map(|mut entry| {
-            func(unsafe { entry.get_mut_unchecked() });
-            entry
-        })
+    pub fn close(&self) {
+        self.closed.store(true, SeqCst);
+        // Pairs with the fence in `recycle`, so that an item pushed by a
+        // concurrent recycle is either drained here or there.
+        fence(SeqCst);
+        let held_back = std::mem::take(&mut self.lock_held_back().items);
+        for (item, _, _) in held_back {
+            self.discard(item);
+        }
+        while let Some(item) = self.queue.pop() {
+            self.discard(item);
+        }
+        self.notify.notify_waiters();
     }
 
-    /// Pull an owned item from the pool. Return `None` if the pool is empty.
+    /// List where and when the items currently in use were pulled, oldest
+    /// first, to find the call sites leaking items.
     ///
     /// # Example
     ///
     /// ```rust
     /// use concurrent_pool::Pool;
-    /// use std::sync::Arc;
     ///
-    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(2));
-    /// let item1 = pool.pull_owned().unwrap();
-    /// assert_eq!(*item1, 0);
+    /// let pool: Pool<u32> = Pool::with_capacity(2);
+    /// let leaked = pool.pull().unwrap();
+    /// let line = line!() - 1;
+    /// drop(pool.pull().unwrap());
+    /// let outstanding = pool.outstanding();
+    /// assert_eq!(outstanding.len(), 1);
+    /// assert_eq!(outstanding[0].location().line(), line);
     /// ```
-    pub fn pull_owned(self: &Arc<Self>) -> Option<OwnedEntry<T>> {
-        self.pull_inner().map(|item| crate::OwnedEntry {
-            item: Some(item),
-            pool: self.clone(),
-        })
+    #[cfg(feature = "instrument")]
+    pub fn outstanding(&self) -> Vec<PullRecord> {
+        let outstanding = self.lock_outstanding();
+        let mut records: Vec<_> = outstanding.values().map(|(record, _)| *record).collect();
+        records.sort_by_key(PullRecord::pulled_at);
+        records
     }
 
-    /// Pull an owned item from the pool and apply a function to it. Return `None` if the pool is empty.
+    /// Take the items set aside by the hook set with
+    /// [`Builder::on_recycle`](crate::Builder::on_recycle), freeing their
+    /// slots.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use concurrent_pool::Pool;
-    /// use std::sync::Arc;
+    /// use concurrent_pool::{Builder, RecycleDecision};
     ///
-    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(2));
-    /// let item1 = pool.pull_owned_with(|x| *x = 42).unwrap();
-    /// assert_eq!(*item1, 42);
+    /// let pool = Builder::<u32>::new()
+    ///     .capacity(2)
+    ///     .on_recycle(|n| match *n {
+    ///         0 => RecycleDecision::Reuse,
+    ///         _ => RecycleDecision::Quarantine,
+    ///     })
+    ///     .build();
+    /// *pool.pull().unwrap() = 7;
+    /// assert_eq!(pool.quarantined(), 1);
+    /// assert_eq!(pool.allocated(), 1);
+    /// assert_eq!(pool.take_quarantined(), vec![7]);
+    /// assert_eq!(pool.allocated(), 0);
     /// ```
-    pub fn pull_owned_with<F>(self: &Arc<Self>, func: F) -> Option<OwnedEntry<T>>
-    where
-        F: FnOnce(&mut T),
-    {
-        self.pull_owned().map(|mut entry| {
-            func(unsafe { entry.get_mut_unchecked() });
-            entry
-        })
+    pub fn take_quarantined(&self) -> Vec<T> {
+        let items = std::mem::take(&mut self.lock_quarantined().0);
+        items.into_iter().map(|item| self.detach(item)).collect()
     }
 
-    /// Internal method to pull an item from the pool.
-    fn pull_inner(&self) -> Option<Prc<T>> {
-        match self.queue.pop() {
-            None => {
-                if !self.additional_allocated.load(Relaxed) {
-                    self.additional_allocated.store(true, Relaxed);
-                }
-                if self.config.need_process_reclamation {
-                    self.surpluspulls.store(0, SeqCst);
-                }
+    /// Get the number of items set aside by the recycle hook.
+    pub fn quarantined(&self) -> usize {
+        self.lock_quarantined().0.len()
+    }
 
-                match self.allocated.fetch_update(AcqRel, Acquire, |current| {
-                    match current < self.config.capacity {
-                        true => Some(current + 1),
-                        false => None,
-                    }
-                }) {
-                    Ok(_) => Some(Prc::new(T::default())),
-                    Err(_) => None,
-                }
-            }
-            Some(item) => {
-                if self.config.need_process_reclamation {
-                    let left = self.queue.len();
-                    if left >= self.config.idle_threshold_for_surpluspull {
-                        let surpluspulls = self.surpluspulls.fetch_add(1, Relaxed) + 1;
-                        if surpluspulls >= self.config.surpluspull_threshold_for_reclaim
-                            && self.additional_allocated.load(Relaxed)
-                        {
-                            self.reclaim();
-                        }
-                    } else {
-                        self.surpluspulls.store(0, Relaxed);
-                    }
-                }
+    fn lock_held_back(&self) -> std::sync::MutexGuard<'_, HeldBack<T>> {
+        self.held_back.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_tokens(&self) -> std::sync::MutexGuard<'_, TokenSlots<T>> {
+        self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_quarantined(&self) -> std::sync::MutexGuard<'_, Quarantined<T>> {
+        self.quarantined.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Check whether the pool has been closed with [`Pool::close`].
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(SeqCst)
+    }
+
+    /// Get in used items count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(10);
+    /// assert_eq!(pool.in_use(), 0);
+    /// let item = pool.pull().unwrap();
+    /// assert_eq!(pool.in_use(), 1);
+    /// let item2 = pool.pull().unwrap();
+    /// assert_eq!(pool.in_use(), 2);
+    /// ```
+    pub fn in_use(&self) -> usize {
+        self.allocated.load(Relaxed) - self.queue.len()
+    }
+
+    /// Get allocated items count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool = Pool::<usize>::new(2, 5);
+    /// assert_eq!(pool.allocated(), 2);
+    /// ```
+    pub fn allocated(&self) -> usize {
+        self.allocated.load(Acquire)
+    }
+
+    /// Get available items count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(10);
+    /// assert_eq!(pool.available(), 10);
+    /// let item = pool.pull().unwrap();
+    /// assert_eq!(pool.available(), 9);
+    /// ```
+    pub fn available(&self) -> usize {
+        self.config.capacity.saturating_sub(self.in_use())
+    }
+
+    /// Get available items count without allocation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::new(2, 5);
+    /// assert_eq!(pool.available_noalloc(), 2);
+    /// let item = pool.pull().unwrap();
+    /// assert_eq!(pool.available_noalloc(), 1);
+    /// let item2 = pool.pull().unwrap();
+    /// assert_eq!(pool.available_noalloc(), 0);
+    /// let item3 = pool.pull().unwrap();
+    /// assert_eq!(pool.available_noalloc(), 0);
+    /// drop(item);
+    /// assert_eq!(pool.available_noalloc(), 1);
+    /// ```
+    pub fn available_noalloc(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Check if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(2);
+    /// assert!(!pool.is_empty());
+    /// let item1 = pool.pull().unwrap();
+    /// assert!(!pool.is_empty());
+    /// let item2 = pool.pull().unwrap();
+    /// assert!(pool.is_empty());
+    /// drop(item1);
+    /// assert!(!pool.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.available() == 0
+    }
+
+    /// Get the name of the pool, set with [`Builder::name`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<u32>::new().name("buffers").build();
+    /// assert_eq!(pool.name(), Some("buffers"));
+    /// assert!(format!("{pool:?}").contains("buffers"));
+    /// ```
+    pub fn name(&self) -> Option<&str> {
+        self.config.name.as_deref()
+    }
+
+    /// Get the capacity of the pool.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(10);
+    /// assert_eq!(pool.capacity(), 10);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.config.capacity
+    }
+
+    /// Get the effective configuration of the pool, including the thresholds
+    /// derived from the capacity when they are not set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<u32>::new().capacity(1000).enable_auto_reclaim().build();
+    /// let config = pool.config();
+    /// assert_eq!(config.idle_threshold_for_surpluspull, 50);
+    /// assert_eq!(config.surpluspull_threshold_for_reclaim, 10);
+    /// ```
+    pub fn config(&self) -> ConfigView {
+        ConfigView {
+            capacity: self.config.capacity,
+            prealloc: self.config.prealloc,
+            prealloc_mode: self.config.prealloc_mode,
+            auto_reclaim: self.reclaim.auto_reclaim.load(Relaxed),
+            surpluspull_threshold_for_reclaim: self.reclaim.surpluspull_threshold.load(Relaxed),
+            idle_threshold_for_surpluspull: self.reclaim.idle_threshold.load(Relaxed),
+            backoff: self.config.backoff,
+            clear_policy: self.config.clear_policy,
+            min_idle: self.config.min_idle,
+            soft_capacity: self.config.soft_capacity,
+            on_exhausted: self.config.on_exhausted,
+            reclaim_batch: self.config.reclaim_batch,
+            idle_high_watermark: self.config.idle_high_watermark,
+            idle_low_watermark: self.config.idle_low_watermark,
+            idle_timeout: self.config.idle_timeout,
+            max_lifetime: self.config.max_lifetime,
+            max_uses: self.config.max_uses,
+            propagate_clear_panics: self.config.propagate_clear_panics,
+            quarantine: self.config.quarantine,
+        }
+    }
+
+    /// Allocate up to `n` additional idle items right away, bounded by the
+    /// capacity, so that upcoming pulls do not pay the cost of allocation.
+    /// Return the number of allocated items.
+    ///
+    /// Allocation stops at the first failure of a fallible factory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::new(2, 5);
+    /// assert_eq!(pool.warm_up(2), 2);
+    /// assert_eq!(pool.available_noalloc(), 4);
+    /// assert_eq!(pool.warm_up(10), 1);
+    /// assert_eq!(pool.allocated(), 5);
+    /// ```
+    pub fn warm_up(&self, n: usize) -> usize {
+        let accounted = self.try_allocate(n, false);
+        let created = self.push_new_items(accounted);
+        if created > 0 {
+            self.notify.notify_waiters();
+        }
+        created
+    }
+
+    /// Pull an item from the pool. Return [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(2);
+    /// let item1 = pool.pull().unwrap();
+    /// assert_eq!(*item1, 0);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull(&self) -> Result<Entry<'_, T>, PullError> {
+        self.pull_inner(true).map(|item| Entry {
+            item: Some(item),
+            pool: self,
+        })
+    }
+
+    /// Pull an item from the pool and apply a function to it. Return
+    /// [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// If the function panics, the item is recycled when a clear function is
+    /// configured, and discarded otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(2);
+    /// let item1 = pool.pull_with(|x| *x = 42).unwrap();
+    /// assert_eq!(*item1, 42);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_with<F>(&self, func: F) -> Result<Entry<'_, T>, PullError>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.pull().map(|mut entry| {
+            self.guarded(&mut entry.item, func);
+            entry
+        })
+    }
+
+    /// Pull an item from the pool and clone `src` into it. Return
+    /// [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// The clone goes through [`Clone::clone_from`], so heap buffers of the
+    /// pooled item are reused when possible.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(1);
+    /// drop(pool.pull_with(|v| v.reserve(64)).unwrap());
+    /// let item = pool.pull_clone_from(&vec![1, 2, 3]).unwrap();
+    /// assert_eq!(*item, [1, 2, 3]);
+    /// assert!(item.capacity() >= 64);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_clone_from(&self, src: &T) -> Result<Entry<'_, T>, PullError>
+    where
+        T: Clone,
+    {
+        self.pull_with(|item| item.clone_from(src))
+    }
+
+    /// Pull an item from the pool and apply the configured clear function to it
+    /// right away. Return [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<String>::new().capacity(1).clear_func(String::clear).build();
+    /// let item = pool.pull_raw_with(|s| s.push_str("hello")).unwrap();
+    /// drop(item);
+    /// assert_eq!(&*pool.pull_clean().unwrap(), "");
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_clean(&self) -> Result<Entry<'_, T>, PullError> {
+        self.pull_with(|item| self.clear_item(item))
+    }
+
+    /// Pull an item from the pool, skipping the configured clear function when
+    /// it is recycled. Return [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<String>::new().capacity(1).clear_func(String::clear).build();
+    /// let mut item = pool.pull_raw().unwrap();
+    /// item.push_str("hello");
+    /// drop(item);
+    /// assert_eq!(&*pool.pull().unwrap(), "hello");
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_raw(&self) -> Result<Entry<'_, T>, PullError> {
+        self.pull()
+            .inspect(|entry| entry.item.as_ref().unwrap().skip_clear())
+    }
+
+    /// Pull an item from the pool and apply a function to it, skipping the
+    /// configured clear function when it is recycled. Return
+    /// [`PullError::Exhausted`] if the pool is empty.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_raw_with<F>(&self, func: F) -> Result<Entry<'_, T>, PullError>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.pull_raw().map(|mut entry| {
+            self.guarded(&mut entry.item, func);
+            entry
+        })
+    }
+
+    /// Pull an item from the pool and apply a fallible function to it. Return
+    /// [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// If the function returns an error, the item is recycled back into the pool
+    /// and the error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<String> = Pool::with_capacity(1);
+    /// let result = pool.pull_try_with(|s| "42".parse::<u32>().map(|_| s.push_str("42")));
+    /// assert_eq!(&*result.unwrap().unwrap(), "42");
+    /// let result = pool.pull_try_with(|_| "x".parse::<u32>().map(|_| ()));
+    /// assert!(result.unwrap().is_err());
+    /// assert_eq!(pool.available(), 1);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_try_with<F, E>(&self, func: F) -> Result<Result<Entry<'_, T>, E>, PullError>
+    where
+        F: FnOnce(&mut T) -> Result<(), E>,
+    {
+        self.pull()
+            .map(|mut entry| self.guarded(&mut entry.item, func).map(|_| entry))
+    }
+
+    /// Pull a unique item from the pool. Return [`PullError::Exhausted`] if the
+    /// pool is empty.
+    ///
+    /// A [`UniqueEntry`] can not be cloned, so it skips reference counting and
+    /// gives mutable access to the item directly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(2);
+    /// let mut item = pool.pull_unique().unwrap();
+    /// item.extend_from_slice(b"hello");
+    /// assert_eq!(&*item, b"hello");
+    /// drop(item);
+    /// assert_eq!(pool.available(), 2);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_unique(&self) -> Result<UniqueEntry<'_, T>, PullError> {
+        self.pull_inner_unique(true).map(|(item, _)| UniqueEntry {
+            item: Some(item),
+            pool: self,
+        })
+    }
+
+    /// Pull a pinned item from the pool. Return [`PullError::Exhausted`] if the
+    /// pool is empty.
+    ///
+    /// The address of the item never changes until it is dropped. When the last
+    /// reference is dropped, the item is dropped in place and replaced with a
+    /// fresh value from the factory instead of applying the clear function, so it is safe to
+    /// pin `!Unpin` types such as async state machines.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::marker::PhantomPinned;
+    ///
+    /// #[derive(Default)]
+    /// struct Task {
+    ///     polled: u32,
+    ///     _pin: PhantomPinned,
+    /// }
+    ///
+    /// let pool: Pool<Task> = Pool::with_capacity(1);
+    /// let mut task = pool.pull_pinned().unwrap();
+    /// unsafe { task.as_mut().get_unchecked_mut().polled += 1 };
+    /// assert_eq!(task.polled, 1);
+    /// drop(task);
+    /// assert_eq!(pool.pull().unwrap().polled, 0);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_pinned(&self) -> Result<Pin<Entry<'_, T>>, PullError> {
+        self.pull_inner(true).map(|item| {
+            item.pin();
+            let entry = Entry {
+                item: Some(item),
+                pool: self,
+            };
+            // The item lives in a heap allocation which is never moved, and it
+            // is dropped in place before the memory is reused.
+            unsafe { Pin::new_unchecked(entry) }
+        })
+    }
+
+    /// Pull an item from the pool. If the pool is empty, wrap the value created by
+    /// `func` instead. The fallback value does not belong to the pool, it is
+    /// dropped instead of being recycled when the last reference is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(1);
+    /// let item1 = pool.pull_or_else(|| 42);
+    /// assert_eq!(*item1, 0);
+    /// let item2 = pool.pull_or_else(|| 42);
+    /// assert_eq!(*item2, 42);
+    /// drop(item2);
+    /// assert_eq!(pool.available(), 0);
+    /// assert_eq!(pool.allocated(), 1);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_or_else<F>(&self, func: F) -> Entry<'_, T>
+    where
+        F: FnOnce() -> T,
+    {
+        let item = self
+            .pull_inner(false)
+            .unwrap_or_else(|_| Prc::new_unpooled(func(), self.new_metadata()));
+        Entry {
+            item: Some(item),
+            pool: self,
+        }
+    }
+
+    /// Pull a slot from the pool and move `value` into it, so that it is
+    /// recycled afterwards. The previous content of a reused slot is dropped.
+    /// Return the value back along with the error if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<Vec<u8>> = Pool::with_capacity(1);
+    /// let item = pool.pull_from(b"from network".to_vec()).unwrap();
+    /// assert_eq!(&*item, b"from network");
+    /// let (value, _) = pool.pull_from(vec![1]).unwrap_err();
+    /// assert_eq!(value, vec![1]);
+    /// drop(item);
+    /// assert_eq!(&*pool.pull().unwrap(), b"from network");
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_from(&self, value: T) -> Result<Entry<'_, T>, (T, PullError)> {
+        self.pull_inner_from(value).map(|item| Entry {
+            item: Some(item),
+            pool: self,
+        })
+    }
+
+    /// Pull an item from the pool and deserialize into it in place, so that
+    /// incoming messages reuse the allocations of pooled items. Fail with a
+    /// custom deserialization error if the pool is empty.
+    ///
+    /// `&Pool<T>` also implements [`DeserializeSeed`](serde::de::DeserializeSeed),
+    /// to deserialize pooled items nested in other values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<Vec<u32>> = Pool::with_capacity(1);
+    /// let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+    /// let item = pool.pull_deserialize(&mut de).unwrap();
+    /// assert_eq!(*item, vec![1, 2, 3]);
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_deserialize<'de, D>(&self, deserializer: D) -> Result<Entry<'_, T>, D::Error>
+    where
+        T: serde::Deserialize<'de>,
+        D: serde::Deserializer<'de>,
+    {
+        serde::de::DeserializeSeed::deserialize(self, deserializer)
+    }
+
+    /// Pull an item leased for `duration`, e.g. to keep a hung tenant from
+    /// holding an item forever. Once the lease has expired, the pool revokes it
+    /// during [`Pool::maintain`] or when it is exhausted, blocked pulls waking
+    /// up at the deadline to do so: the function set with
+    /// [`Builder::on_lease_revoked`](crate::Builder::on_lease_revoked) is
+    /// called, and the item stops counting as allocated so that a replacement
+    /// can be allocated. The revoked item is destroyed once its entry is
+    /// dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(1);
+    /// let leased = pool.pull_leased(Duration::from_millis(1)).unwrap();
+    /// std::thread::sleep(Duration::from_millis(10));
+    /// let item = pool.pull().unwrap();
+    /// assert!(leased.is_revoked());
+    /// assert_eq!(pool.in_use(), 1);
+    /// drop(leased);
+    /// assert_eq!(pool.allocated(), 1);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_leased(&self, duration: Duration) -> Result<LeasedEntry<'_, T>, PullError> {
+        let entry = self.pull()?;
+        let lease = Arc::new(Lease::new(Instant::now() + duration));
+        let mut leases = self.lock_leases();
+        leases.retain(|lease| lease.is_active());
+        leases.push(lease.clone());
+        self.active_leases.fetch_add(1, Release);
+        drop(leases);
+        Ok(LeasedEntry::new(entry, lease))
+    }
+
+    /// Pull an item from the pool, blocking the current thread until an item is
+    /// available. Return [`PullError::Timeout`] if no item is available before
+    /// the timeout elapses.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Pool, PullError};
+    /// use std::time::Duration;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(1);
+    /// let item = pool.pull_timeout(Duration::from_millis(10)).unwrap();
+    /// let result = pool.pull_timeout(Duration::from_millis(10));
+    /// assert_eq!(result.unwrap_err(), PullError::Timeout);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_timeout(&self, timeout: Duration) -> Result<Entry<'_, T>, PullError> {
+        self.pull_inner_until(Instant::now().checked_add(timeout), None)
+            .map(|item| Entry {
+                item: Some(item),
+                pool: self,
+            })
+    }
+
+    /// Pull an item from the pool, blocking the current thread until an item is
+    /// available. Return [`PullError::Timeout`] if no item is available before
+    /// the deadline.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Pool, PullError};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(1);
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// let item = pool.pull_until(deadline).unwrap();
+    /// assert_eq!(pool.pull_until(deadline).unwrap_err(), PullError::Timeout);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_until(&self, deadline: Instant) -> Result<Entry<'_, T>, PullError> {
+        self.pull_inner_until(Some(deadline), None)
+            .map(|item| Entry {
+                item: Some(item),
+                pool: self,
+            })
+    }
+
+    /// Pull an item from the pool, blocking the current thread until an item is
+    /// available or the interrupter is interrupted. Return
+    /// [`PullError::Interrupted`] in the latter case.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Pool, PullError};
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(1);
+    /// let interrupter = pool.interrupter();
+    /// let item = pool.pull_interruptible(&interrupter).unwrap();
+    /// std::thread::scope(|s| {
+    ///     let handle = s.spawn(|| pool.pull_interruptible(&interrupter).map(|_| ()));
+    ///     interrupter.interrupt();
+    ///     assert_eq!(handle.join().unwrap(), Err(PullError::Interrupted));
+    /// });
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_interruptible(&self, interrupter: &Interrupter) -> Result<Entry<'_, T>, PullError> {
+        self.pull_inner_until(None, Some(interrupter))
+            .map(|item| Entry {
+                item: Some(item),
+                pool: self,
+            })
+    }
+
+    /// Pull an item from the pool, reporting whether it was reused from the pool
+    /// or freshly allocated. Return [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Pool, PullKind};
+    ///
+    /// let pool: Pool<u32> = Pool::new(1, 2);
+    /// let (item1, kind1) = pool.pull_tracked().unwrap();
+    /// assert_eq!(kind1, PullKind::Reused);
+    /// let (item2, kind2) = pool.pull_tracked().unwrap();
+    /// assert_eq!(kind2, PullKind::FreshlyAllocated);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_tracked(&self) -> Result<(Entry<'_, T>, PullKind), PullError> {
+        self.pull_inner_tracked(true).map(|(item, kind)| {
+            let entry = Entry {
+                item: Some(item),
+                pool: self,
+            };
+            (entry, kind)
+        })
+    }
+
+    /// Pull `n` items from the pool at once. Return [`PullError::Exhausted`] if
+    /// the pool can not provide all of them, in which case no item is pulled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::new(2, 5);
+    /// let items = pool.pull_n(4).unwrap();
+    /// assert_eq!(items.len(), 4);
+    /// assert!(pool.pull_n(2).is_err());
+    /// assert_eq!(pool.available(), 1);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_n(&self, n: usize) -> Result<Vec<Entry<'_, T>>, PullError> {
+        self.pull_inner_n(n, true).map(|items| {
+            items
+                .into_iter()
+                .map(|item| Entry {
+                    item: Some(item),
+                    pool: self,
+                })
+                .collect()
+        })
+    }
+
+    /// Pull as many items as possible from the pool, up to `n`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::new(2, 5);
+    /// let items = pool.pull_up_to(8);
+    /// assert_eq!(items.len(), 5);
+    /// assert!(pool.pull_up_to(2).is_empty());
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_up_to(&self, n: usize) -> Vec<Entry<'_, T>> {
+        self.pull_inner_n(n, false)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| Entry {
+                item: Some(item),
+                pool: self,
+            })
+            .collect()
+    }
+
+    /// Pull an owned item from the pool. Return [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(2));
+    /// let item1 = pool.pull_owned().unwrap();
+    /// assert_eq!(*item1, 0);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned(self: &Arc<Self>) -> Result<OwnedEntry<T>, PullError> {
+        self.pull_inner(true).map(|item| crate::OwnedEntry {
+            item: Some(item),
+            pool: self.clone(),
+        })
+    }
+
+    /// Pull a pinned owned item from the pool. Return [`PullError::Exhausted`]
+    /// if the pool is empty.
+    ///
+    /// See [`Pool::pull_pinned`] for the guarantees of pinned items.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_pinned(self: &Arc<Self>) -> Result<Pin<OwnedEntry<T>>, PullError> {
+        self.pull_inner(true).map(|item| {
+            item.pin();
+            let entry = OwnedEntry {
+                item: Some(item),
+                pool: self.clone(),
+            };
+            // The item lives in a heap allocation which is never moved, and it
+            // is dropped in place before the memory is reused.
+            unsafe { Pin::new_unchecked(entry) }
+        })
+    }
+
+    /// Pull an owned item from the pool and apply a function to it. Return
+    /// [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// If the function panics, the item is recycled when a clear function is
+    /// configured, and discarded otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(2));
+    /// let item1 = pool.pull_owned_with(|x| *x = 42).unwrap();
+    /// assert_eq!(*item1, 42);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_with<F>(self: &Arc<Self>, func: F) -> Result<OwnedEntry<T>, PullError>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.pull_owned().map(|mut entry| {
+            self.guarded(&mut entry.item, func);
+            entry
+        })
+    }
+
+    /// Pull an owned item from the pool and apply the configured clear function
+    /// to it right away. Return [`PullError::Exhausted`] if the pool is empty.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_clean(self: &Arc<Self>) -> Result<OwnedEntry<T>, PullError> {
+        self.pull_owned_with(|item| self.clear_item(item))
+    }
+
+    /// Pull an owned item from the pool, skipping the configured clear function
+    /// when it is recycled. Return [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Builder;
+    ///
+    /// let pool = Builder::<Vec<u8>>::new().capacity(1).clear_func(Vec::clear).build_shared();
+    /// let mut item = pool.pull_owned_raw().unwrap();
+    /// item.push(1);
+    /// drop(item);
+    /// assert!(pool.pull_owned_clean().unwrap().is_empty());
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_raw(self: &Arc<Self>) -> Result<OwnedEntry<T>, PullError> {
+        self.pull_owned()
+            .inspect(|entry| entry.item.as_ref().unwrap().skip_clear())
+    }
+
+    /// Pull an owned item from the pool and apply a fallible function to it.
+    /// Return [`PullError::Exhausted`] if the pool is empty.
+    ///
+    /// If the function returns an error, the item is recycled back into the pool
+    /// and the error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let result = pool.pull_owned_try_with(|x| "42".parse().map(|v| *x = v));
+    /// assert_eq!(*result.unwrap().unwrap(), 42);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_try_with<F, E>(
+        self: &Arc<Self>,
+        func: F,
+    ) -> Result<Result<OwnedEntry<T>, E>, PullError>
+    where
+        F: FnOnce(&mut T) -> Result<(), E>,
+    {
+        self.pull_owned()
+            .map(|mut entry| self.guarded(&mut entry.item, func).map(|_| entry))
+    }
+
+    /// Pull a slot from the pool and move `value` into it as an owned item, so
+    /// that it is recycled afterwards. The previous content of a reused slot is
+    /// dropped. Return the value back along with the error if the pool is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<String>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned_from("parsed".to_string()).unwrap();
+    /// assert_eq!(&*item, "parsed");
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_from(self: &Arc<Self>, value: T) -> Result<OwnedEntry<T>, (T, PullError)> {
+        self.pull_inner_from(value).map(|item| OwnedEntry {
+            item: Some(item),
+            pool: self.clone(),
+        })
+    }
+
+    /// Pull an owned item from the pool, blocking the current thread until an
+    /// item is available. Return [`PullError::Timeout`] if no item is available
+    /// before the timeout elapses.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned().unwrap();
+    /// let clone_pool = pool.clone();
+    /// let handle = std::thread::spawn(move || {
+    ///     clone_pool.pull_owned_timeout(Duration::from_secs(10)).is_ok()
+    /// });
+    /// drop(item);
+    /// assert!(handle.join().unwrap());
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_timeout(
+        self: &Arc<Self>,
+        timeout: Duration,
+    ) -> Result<OwnedEntry<T>, PullError> {
+        self.pull_inner_until(Instant::now().checked_add(timeout), None)
+            .map(|item| OwnedEntry {
+                item: Some(item),
+                pool: self.clone(),
+            })
+    }
+
+    /// Pull an owned item from the pool, blocking the current thread until an
+    /// item is available. Return [`PullError::Timeout`] if no item is available
+    /// before the deadline.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Pool, PullError};
+    /// use std::sync::Arc;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// let item = pool.pull_owned_until(deadline).unwrap();
+    /// let result = pool.pull_owned_until(deadline);
+    /// assert!(matches!(result, Err(PullError::Timeout)));
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_until(
+        self: &Arc<Self>,
+        deadline: Instant,
+    ) -> Result<OwnedEntry<T>, PullError> {
+        self.pull_inner_until(Some(deadline), None)
+            .map(|item| OwnedEntry {
+                item: Some(item),
+                pool: self.clone(),
+            })
+    }
+
+    /// Pull an owned item from the pool, blocking the current thread until an
+    /// item is available or the interrupter is interrupted. Return
+    /// [`PullError::Interrupted`] in the latter case.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Pool, PullError};
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let interrupter = pool.interrupter();
+    /// let item = pool.pull_owned().unwrap();
+    /// let clone_pool = pool.clone();
+    /// let clone_interrupter = interrupter.clone();
+    /// let handle = std::thread::spawn(move || {
+    ///     clone_pool.pull_owned_interruptible(&clone_interrupter).is_err()
+    /// });
+    /// interrupter.interrupt();
+    /// assert!(handle.join().unwrap());
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_interruptible(
+        self: &Arc<Self>,
+        interrupter: &Interrupter,
+    ) -> Result<OwnedEntry<T>, PullError> {
+        self.pull_inner_until(None, Some(interrupter))
+            .map(|item| OwnedEntry {
+                item: Some(item),
+                pool: self.clone(),
+            })
+    }
+
+    /// Pull an owned item from the pool, reporting whether it was reused from the
+    /// pool or freshly allocated. Return [`PullError::Exhausted`] if the pool is
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Pool, PullKind};
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::new(0, 1));
+    /// let (item, kind) = pool.pull_owned_tracked().unwrap();
+    /// assert_eq!(kind, PullKind::FreshlyAllocated);
+    /// drop(item);
+    /// let (item, kind) = pool.pull_owned_tracked().unwrap();
+    /// assert_eq!(kind, PullKind::Reused);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_tracked(self: &Arc<Self>) -> Result<(OwnedEntry<T>, PullKind), PullError> {
+        self.pull_inner_tracked(true).map(|(item, kind)| {
+            let entry = OwnedEntry {
+                item: Some(item),
+                pool: self.clone(),
+            };
+            (entry, kind)
+        })
+    }
+
+    /// Pull `n` owned items from the pool at once. Return [`PullError::Exhausted`]
+    /// if the pool can not provide all of them, in which case no item is pulled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(3));
+    /// let items = pool.pull_owned_n(3).unwrap();
+    /// assert_eq!(items.len(), 3);
+    /// assert!(pool.pull_owned_n(1).is_err());
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_n(self: &Arc<Self>, n: usize) -> Result<Vec<OwnedEntry<T>>, PullError> {
+        self.pull_inner_n(n, true).map(|items| {
+            items
+                .into_iter()
+                .map(|item| OwnedEntry {
+                    item: Some(item),
+                    pool: self.clone(),
+                })
+                .collect()
+        })
+    }
+
+    /// Pull as many owned items as possible from the pool, up to `n`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(3));
+    /// let items = pool.pull_owned_up_to(5);
+    /// assert_eq!(items.len(), 3);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_up_to(self: &Arc<Self>, n: usize) -> Vec<OwnedEntry<T>> {
+        self.pull_inner_n(n, false)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| OwnedEntry {
+                item: Some(item),
+                pool: self.clone(),
+            })
+            .collect()
+    }
+
+    /// Reserve `n` items so that they can be pulled later without failing.
+    /// Return [`PullError::Exhausted`] if the pool can not provide all of them.
+    ///
+    /// Reserved items are counted as in use. Idle items are taken out of the
+    /// pool, and the missing ones are accounted as allocated and created on
+    /// demand. Unused reserved items are given back when the [`Reservation`]
+    /// is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::new(1, 3);
+    /// let mut reservation = pool.reserve(2).unwrap();
+    /// assert_eq!(pool.available(), 1);
+    /// assert!(pool.reserve(2).is_err());
+    /// let item = reservation.pull().unwrap();
+    /// assert_eq!(reservation.remaining(), 1);
+    /// drop(reservation);
+    /// assert_eq!(pool.available(), 2);
+    /// ```
+    pub fn reserve(&self, n: usize) -> Result<Reservation<'_, T>, PullError> {
+        self.take_n(n, true)
+            .map(|(items, allocated)| Reservation::new(self, items, allocated))
+    }
+
+    /// Pull an owned item from the pool. If the pool is empty, wrap the value
+    /// created by `func` instead. The fallback value does not belong to the pool,
+    /// it is dropped instead of being recycled when the last reference is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let item1 = pool.pull_owned_or_else(|| 42);
+    /// assert_eq!(*item1, 0);
+    /// let item2 = pool.pull_owned_or_else(|| 42);
+    /// assert_eq!(*item2, 42);
+    /// ```
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_owned_or_else<F>(self: &Arc<Self>, func: F) -> OwnedEntry<T>
+    where
+        F: FnOnce() -> T,
+    {
+        let item = self
+            .pull_inner(false)
+            .unwrap_or_else(|_| Prc::new_unpooled(func(), self.new_metadata()));
+        OwnedEntry {
+            item: Some(item),
+            pool: self.clone(),
+        }
+    }
+
+    /// Poll to pull an owned item from the pool.
+    ///
+    /// Return `Poll::Ready` with the item if one is available. If the pool is
+    /// exhausted, the waker of `cx` is registered and woken up when an item is
+    /// recycled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let item = match pool.poll_pull_owned(&mut cx) {
+    ///     Poll::Ready(item) => item.unwrap(),
+    ///     Poll::Pending => unreachable!(),
+    /// };
+    /// assert!(pool.poll_pull_owned(&mut cx).is_pending());
+    /// drop(item);
+    /// assert!(pool.poll_pull_owned(&mut cx).is_ready());
+    /// ```
+    pub fn poll_pull_owned(
+        self: &Arc<Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<OwnedEntry<T>, PullError>> {
+        match self.try_pull_owned() {
+            Err(PullError::Exhausted) => {}
+            result => return Poll::Ready(result),
+        }
+        self.notify.register(cx.waker());
+        // Check again in case an item was recycled before the waker was registered.
+        match self.try_pull_owned() {
+            Err(PullError::Exhausted) => Poll::Pending,
+            result => Poll::Ready(result),
+        }
+    }
+
+    /// Convert a [`PoolToken`] back into the [`OwnedEntry`] it was created from.
+    /// Return `None` if the token is stale, e.g. it has already been converted
+    /// back, or was not created by this pool.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let token = pool.pull_owned_with(|x| *x = 42).unwrap().into_raw();
+    /// let item = pool.entry_from_raw(token).unwrap();
+    /// assert_eq!(*item, 42);
+    /// assert!(pool.entry_from_raw(token).is_none());
+    /// ```
+    pub fn entry_from_raw(self: &Arc<Self>, token: PoolToken) -> Option<OwnedEntry<T>> {
+        let item = self.lock_tokens().take(token)?;
+        Some(OwnedEntry {
+            item: Some(item),
+            pool: self.clone(),
+        })
+    }
+
+    /// Store an item until its token is converted back.
+    pub(crate) fn store_token(&self, item: Prc<T>) -> PoolToken {
+        self.lock_tokens().insert(item)
+    }
+
+    /// Create an [`Interrupter`] to abort blocking pulls of this pool.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    ///
+    /// let pool: Pool<u32> = Pool::with_capacity(1);
+    /// let interrupter = pool.interrupter();
+    /// assert!(!interrupter.is_interrupted());
+    /// ```
+    pub fn interrupter(&self) -> Interrupter {
+        Interrupter::new(self.notify.clone())
+    }
+
+    /// Wait until the pool has available items.
+    ///
+    /// The returned future completes immediately if there are available items.
+    /// Otherwise, it completes when an item is recycled back into the pool.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(1));
+    /// let item = pool.pull_owned().unwrap();
+    /// let handle = std::thread::spawn(move || drop(item));
+    /// futures::executor::block_on(pool.notified());
+    /// handle.join().unwrap();
+    /// assert_eq!(pool.available(), 1);
+    /// ```
+    pub fn notified(&self) -> Notified<'_, T> {
+        Notified { pool: self }
+    }
+
+    /// Poll whether the pool has available items, registering the waker of
+    /// `cx` if not.
+    pub(crate) fn poll_available(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.can_pull() {
+            return Poll::Ready(());
+        }
+        self.notify.register(cx.waker());
+        // Check again in case an item was recycled before the waker was registered.
+        match self.can_pull() {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        }
+    }
+
+    /// Create a stream that yields owned items as soon as they are available.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::Pool;
+    /// use futures::StreamExt;
+    /// use std::sync::Arc;
+    ///
+    /// let pool: Arc<Pool<u32>> = Arc::new(Pool::with_capacity(2));
+    /// let mut stream = pool.entries_stream();
+    /// futures::executor::block_on(async {
+    ///     let item1 = stream.next().await.unwrap().unwrap();
+    ///     let item2 = stream.next().await.unwrap().unwrap();
+    ///     assert_eq!(pool.available(), 0);
+    ///     drop(item1);
+    ///     let item3 = stream.next().await.unwrap().unwrap();
+    ///     assert_eq!(pool.available(), 0);
+    /// });
+    /// ```
+    #[cfg(feature = "stream")]
+    pub fn entries_stream(self: &Arc<Self>) -> crate::EntriesStream<T> {
+        crate::EntriesStream::new(self.clone())
+    }
+
+    /// Pull an item from the pool without ever blocking, whatever the
+    /// exhausted policy. Used by the wrappers falling back to something else
+    /// when the pool is exhausted.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub(crate) fn try_pull(&self) -> Result<Entry<'_, T>, PullError> {
+        self.pull_inner(false).map(|item| Entry {
+            item: Some(item),
+            pool: self,
+        })
+    }
+
+    /// Pull an owned item from the pool without ever blocking, whatever the
+    /// exhausted policy.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub(crate) fn try_pull_owned(self: &Arc<Self>) -> Result<OwnedEntry<T>, PullError> {
+        self.pull_inner(false).map(|item| OwnedEntry {
+            item: Some(item),
+            pool: self.clone(),
+        })
+    }
+
+    /// Internal method to pull an item from the pool.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    fn pull_inner(&self, block: bool) -> Result<Prc<T>, PullError> {
+        self.pull_inner_tracked(block).map(|(item, _)| item)
+    }
+
+    /// Internal method to pull an item from the pool, reporting whether it was
+    /// reused or freshly allocated.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    fn pull_inner_tracked(&self, block: bool) -> Result<(Prc<T>, PullKind), PullError> {
+        self.pull_inner_unique(block).map(|(item, kind)| {
+            item.inc_ref();
+            (item, kind)
+        })
+    }
+
+    /// Internal method to pull an item from the pool without touching its
+    /// reference count, which is left at zero. If `block` is set, wait until an
+    /// item is available if the pool is exhausted and the policy is
+    /// [`ExhaustedPolicy::Block`]. Only the public blocking pulls set it, so that
+    /// fallbacks and polls never wait.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    fn pull_inner_unique(&self, block: bool) -> Result<(Prc<T>, PullKind), PullError> {
+        loop {
+            match self.pull_inner_unique_nowait() {
+                Err(PullError::Exhausted)
+                    if block && self.config.on_exhausted == ExhaustedPolicy::Block =>
+                {
+                    // Wake up when the earliest lease expires to revoke it.
+                    self.notify
+                        .wait_until(self.next_lease_deadline(), || self.can_pull());
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Internal method to pull an item from the pool without touching its
+    /// reference count, returning [`PullError::Exhausted`] right away if the
+    /// pool is exhausted.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    fn pull_inner_unique_nowait(&self) -> Result<(Prc<T>, PullKind), PullError> {
+        let (mut item, kind) = match self.pull_inner_unique_or(|| self.new_value()) {
+            // Expired leases free their slots for replacements.
+            Err(PullError::Exhausted) if self.revoke_expired_leases() > 0 => {
+                self.pull_inner_unique_or(|| self.new_value())?
+            }
+            result => result?,
+        };
+        self.before_pull(&mut item, kind == PullKind::Reused);
+        Ok((item, kind))
+    }
+
+    /// Internal method to pull an item from the pool without touching its
+    /// reference count, creating the value of a freshly allocated item with
+    /// `alloc`.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    fn pull_inner_unique_or<F>(&self, alloc: F) -> Result<(Prc<T>, PullKind), PullError>
+    where
+        F: FnOnce() -> Result<T, FactoryError>,
+    {
+        if self.is_closed() {
+            return Err(PullError::Closed);
+        }
+        let popped = self
+            .pop_with_backoff()
+            .or_else(|| self.fill_lazy_prealloc().then(|| self.queue.pop())?);
+        let result = match popped {
+            None => match self.try_allocate(1, true) == 1 || self.try_grow() {
+                true => match alloc() {
+                    Ok(value) => Ok((
+                        Prc::new_zero(value, self.new_metadata()),
+                        PullKind::FreshlyAllocated,
+                    )),
+                    Err(err) => {
+                        self.release(Vec::new(), 1);
+                        Err(PullError::Factory(err))
+                    }
+                },
+                _ => Err(PullError::Exhausted),
+            },
+            Some(mut item) if item.is_uninit() => match alloc() {
+                // The slot has never been used, so it is accounted as reused
+                // but its value is created now.
+                Ok(value) => {
+                    unsafe { Prc::init(&mut item, value) };
+                    Ok((item, PullKind::FreshlyAllocated))
+                }
+                Err(err) => {
+                    self.release(vec![item], 0);
+                    Err(PullError::Factory(err))
+                }
+            },
+            Some(item) => {
+                if self.reclaim.enabled.load(Relaxed) && self.config.idle_high_watermark == 0 {
+                    let left = self.queue.len();
+                    if left >= self.reclaim.idle_threshold.load(Relaxed) {
+                        let surpluspulls = self.surpluspulls.fetch_add(1, Relaxed) + 1;
+                        if surpluspulls >= self.reclaim.surpluspull_threshold.load(Relaxed)
+                            && self.additional_allocated.load(Relaxed)
+                        {
+                            self.reclaim();
+                        }
+                    } else {
+                        self.surpluspulls.store(0, Relaxed);
+                    }
+                }
+                Ok((item, PullKind::Reused))
+            }
+        };
+        if let Ok((item, _)) = &result {
+            item.checkout();
+            self.replenish_idle();
+        }
+        result
+    }
+
+    /// Internal method to pull a slot from the pool holding `value`.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    fn pull_inner_from(&self, value: T) -> Result<Prc<T>, (T, PullError)> {
+        let mut value = Some(value);
+        match self.pull_inner_unique_or(|| Ok(value.take().unwrap())) {
+            Ok((mut item, kind)) => {
+                if kind == PullKind::Reused {
+                    *unsafe { Prc::get_mut_unchecked(&mut item) } = value.take().unwrap();
+                }
+                // The previous value of a reused slot has been overwritten.
+                self.before_pull(&mut item, false);
                 item.inc_ref();
-                Some(item)
+                Ok(item)
+            }
+            Err(err) => Err((value.take().unwrap(), err)),
+        }
+    }
+
+    /// Internal method to pull an item from the pool, blocking until an item is
+    /// available or the deadline is reached. No deadline means waiting forever.
+    ///
+    /// If an interrupter is given, return [`PullError::Interrupted`] once it is
+    /// interrupted instead of waiting.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    fn pull_inner_until(
+        &self,
+        deadline: Option<Instant>,
+        interrupter: Option<&Interrupter>,
+    ) -> Result<Prc<T>, PullError> {
+        let interrupted = || interrupter.is_some_and(Interrupter::is_interrupted);
+        loop {
+            match self.pull_inner_unique_nowait() {
+                Ok((item, _)) => {
+                    item.inc_ref();
+                    return Ok(item);
+                }
+                Err(PullError::Exhausted) => {}
+                Err(err) => return Err(err),
+            }
+            if interrupted() {
+                return Err(PullError::Interrupted);
+            }
+            let wake_at = match (deadline, self.next_lease_deadline()) {
+                (Some(deadline), Some(expiry)) => Some(deadline.min(expiry)),
+                (deadline, expiry) => deadline.or(expiry),
+            };
+            if !self
+                .notify
+                .wait_until(wake_at, || self.can_pull() || interrupted())
+                && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                return Err(PullError::Timeout);
+            }
+        }
+    }
+
+    /// Internal method to pull `n` items from the pool in a single pass.
+    ///
+    /// If `exact` is `true`, return [`PullError::Exhausted`] unless all `n`
+    /// items can be pulled. Otherwise, pull as many items as possible up to `n`.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    fn pull_inner_n(&self, n: usize, exact: bool) -> Result<Vec<Prc<T>>, PullError> {
+        let (mut items, allocated) = self.take_n(n, exact)?;
+        let mut fresh = Vec::new();
+        for item in &mut items {
+            match self.init_slot(item) {
+                Ok(initialized) => fresh.push(initialized),
+                Err(err) => {
+                    self.release(items, allocated);
+                    return Err(PullError::Factory(err));
+                }
+            }
+        }
+        let values = match (0..allocated)
+            .map(|_| self.new_value())
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(values) => values,
+            Err(err) => {
+                self.release(items, allocated);
+                return Err(PullError::Factory(err));
+            }
+        };
+        for (item, fresh) in items.iter_mut().zip(fresh) {
+            item.checkout();
+            self.before_pull(item, !fresh);
+            item.inc_ref();
+        }
+        for value in values {
+            let mut item = Prc::new(value, self.new_metadata());
+            self.before_pull(&mut item, false);
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    /// Take up to `n` idle items from the queue, and account new allocations for
+    /// the missing ones. Return the idle items and the number of accounted
+    /// allocations. The reference count of idle items is left untouched.
+    ///
+    /// If `exact` is `true`, return [`PullError::Exhausted`] unless all `n`
+    /// items can be taken.
+    fn take_n(&self, n: usize, exact: bool) -> Result<(Vec<Prc<T>>, usize), PullError> {
+        let (mut items, mut allocated) = match self.take_n_once(n, exact) {
+            // Expired leases free their slots for replacements.
+            Err(PullError::Exhausted) if self.revoke_expired_leases() > 0 => {
+                return self.take_n_once(n, exact);
+            }
+            result => result?,
+        };
+        let missing = n - items.len() - allocated;
+        if missing > 0 && self.revoke_expired_leases() > 0 {
+            match self.take_n_once(missing, false) {
+                Ok((more, more_allocated)) => {
+                    items.extend(more);
+                    allocated += more_allocated;
+                }
+                Err(err) => {
+                    self.release(items, allocated);
+                    return Err(err);
+                }
+            }
+        }
+        Ok((items, allocated))
+    }
+
+    /// Take up to `n` items in a single pass, as [`Pool::take_n`] does without
+    /// revoking expired leases.
+    fn take_n_once(&self, n: usize, exact: bool) -> Result<(Vec<Prc<T>>, usize), PullError> {
+        if self.is_closed() {
+            return Err(PullError::Closed);
+        }
+        let mut items = Vec::with_capacity(n);
+        while items.len() < n {
+            match self.pop_idle() {
+                Some(item) => items.push(item),
+                None => break,
+            }
+        }
+        let missing = n - items.len();
+        let allocated = match missing {
+            0 => 0,
+            _ => self.try_allocate(missing, exact),
+        };
+        if exact && allocated < missing {
+            self.release(items, 0);
+            return Err(PullError::Exhausted);
+        }
+        self.replenish_idle();
+        Ok((items, allocated))
+    }
+
+    /// Give back idle items taken by [`Pool::take_n`] and unused accounted allocations.
+    pub(crate) fn release(&self, items: Vec<Prc<T>>, allocated: usize) {
+        let released = !items.is_empty() || allocated > 0;
+        for item in items {
+            let _ = self.queue.push(item);
+        }
+        if allocated > 0 {
+            self.allocated.fetch_sub(allocated, Release);
+        }
+        if released {
+            self.notify.notify_waiters();
+        }
+    }
+
+    /// Prepare an item being checked out, which must not be shared yet: clear
+    /// it if `reused` and the clear policy is [`ClearPolicy::OnPull`], then
+    /// apply the hook configured with [`Builder::before_pull`](crate::Builder::before_pull).
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub(crate) fn before_pull(&self, item: &mut Prc<T>, reused: bool) {
+        if self.config.clear_policy == ClearPolicy::OnPull && !item.take_skip_clear() && reused {
+            self.clear_item(unsafe { Prc::get_mut_unchecked(item) });
+        }
+        if let Some(func) = self.config.before_pull {
+            func(unsafe { Prc::get_mut_unchecked(item) })
+        }
+        #[cfg(feature = "instrument")]
+        if item.is_pooled() {
+            let record = PullRecord::new(std::panic::Location::caller());
+            self.lock_outstanding().insert(item.addr(), (record, false));
+        }
+    }
+
+    /// Remove an item which is no longer in use from the outstanding items.
+    #[cfg(feature = "instrument")]
+    fn untrack(&self, item: &Prc<T>) {
+        self.lock_outstanding().remove(&item.addr());
+    }
+
+    #[cfg(feature = "instrument")]
+    fn lock_outstanding(&self) -> std::sync::MutexGuard<'_, HashMap<usize, (PullRecord, bool)>> {
+        self.outstanding.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Construct the value of an idle item popped from the queue if it is a
+    /// [deferred](PreallocMode::Deferred) slot. Return whether it has been
+    /// constructed now.
+    pub(crate) fn init_slot(&self, item: &mut Prc<T>) -> Result<bool, FactoryError> {
+        if !item.is_uninit() {
+            return Ok(false);
+        }
+        let value = self.new_value()?;
+        unsafe { Prc::init(item, value) };
+        Ok(true)
+    }
+
+    /// Create the value of a new item.
+    pub(crate) fn new_value(&self) -> Result<T, FactoryError> {
+        self.config.factory.create()
+    }
+
+    /// Create the metadata attached to a new item.
+    pub(crate) fn new_metadata(&self) -> Option<Metadata> {
+        self.config.metadata.map(|func| func())
+    }
+
+    /// Try to account `n` new allocations within the capacity of the pool.
+    ///
+    /// If `exact` is `true`, either all `n` or none are accounted. Otherwise, as
+    /// many as possible up to `n` are accounted. Return the number of accounted
+    /// allocations.
+    fn try_allocate(&self, n: usize, exact: bool) -> usize {
+        if !self.additional_allocated.load(Relaxed) {
+            self.additional_allocated.store(true, Relaxed);
+        }
+        if self.reclaim.enabled.load(Relaxed) {
+            self.surpluspulls.store(0, SeqCst);
+        }
+
+        let mut accounted = 0;
+        let _ = self.allocated.fetch_update(AcqRel, Acquire, |current| {
+            let free = self.config.capacity.saturating_sub(current);
+            accounted = match exact {
+                true if free >= n => n,
+                true => 0,
+                false => min(n, free),
+            };
+            (accounted > 0).then_some(current + accounted)
+        });
+        accounted
+    }
+
+    /// Create the next chunk of preallocated items into the queue when
+    /// preallocation is [lazy](PreallocMode::Lazy). Return whether any item has
+    /// been created.
+    fn fill_lazy_prealloc(&self) -> bool {
+        let PreallocMode::Lazy { chunk } = self.config.prealloc_mode else {
+            return false;
+        };
+        let mut accounted = 0;
+        let _ = self.allocated.fetch_update(AcqRel, Acquire, |current| {
+            accounted = min(max(chunk, 1), self.config.prealloc.saturating_sub(current));
+            (accounted > 0).then_some(current + accounted)
+        });
+        let created = self.push_new_items(accounted);
+        created > 0
+    }
+
+    /// Allocate idle items up to the configured minimum.
+    fn replenish_idle(&self) {
+        let idle = self.queue.len();
+        if idle < self.config.min_idle {
+            self.warm_up(self.config.min_idle - idle);
+        }
+    }
+
+    /// Create `accounted` new idle items into the queue, stopping at the first
+    /// failure of the factory and giving back the remaining allocations. Return
+    /// the number of created items.
+    fn push_new_items(&self, accounted: usize) -> usize {
+        let mut created = 0;
+        while created < accounted {
+            match self.new_value() {
+                Ok(value) => {
+                    let _ = self.queue.push(Prc::new_zero(value, self.new_metadata()));
+                    created += 1;
+                }
+                Err(_) => break,
+            }
+        }
+        if created < accounted {
+            self.allocated.fetch_sub(accounted - created, Release);
+        }
+        created
+    }
+
+    /// Pop an idle item from the queue, freeing the expired items found first.
+    fn pop_idle(&self) -> Option<Prc<T>> {
+        if self.config.quarantine.is_some() {
+            self.release_held_back();
+        }
+        loop {
+            let item = match self.queue.pop() {
+                Some(item) => item,
+                None => self.take_held_back()?,
+            };
+            if !self.is_expired(&item) && !self.is_worn_out(&item) {
+                return Some(item);
+            }
+            self.free(item);
+        }
+    }
+
+    /// Count a pull, and move the items whose quarantine window is over into
+    /// the queue.
+    fn release_held_back(&self) {
+        let mut held_back = self.lock_held_back();
+        held_back.pulls += 1;
+        let pulls = held_back.pulls;
+        while let Some((_, recycled_pull, recycled_at)) = held_back.items.front() {
+            let over = match self.config.quarantine {
+                Some(QuarantineWindow::Pulls(n)) => pulls > recycled_pull + n,
+                Some(QuarantineWindow::Duration(duration)) => recycled_at.elapsed() >= duration,
+                None => true,
+            };
+            if !over {
+                break;
+            }
+            let (item, _, _) = held_back.items.pop_front().unwrap();
+            let _ = self.queue.push(item);
+        }
+    }
+
+    /// Check whether a pull may succeed, or fail for good because the pool is
+    /// closed. Items held back by the quarantine window count once no item can
+    /// be allocated instead, since pulls take them back then.
+    fn can_pull(&self) -> bool {
+        self.available() > 0
+            || self.is_closed()
+            || (self.config.quarantine.is_some()
+                && self.allocated.load(Acquire) >= self.config.capacity
+                && !self.lock_held_back().items.is_empty())
+    }
+
+    /// Take the oldest item held back by the quarantine window if no item can
+    /// be allocated instead.
+    fn take_held_back(&self) -> Option<Prc<T>> {
+        if self.config.quarantine.is_none() || self.allocated.load(Acquire) < self.config.capacity {
+            return None;
+        }
+        self.lock_held_back()
+            .items
+            .pop_front()
+            .map(|(item, _, _)| item)
+    }
+
+    /// Free the expired idle items, going once through the queue. The other
+    /// items are pushed back in the same order.
+    fn evict_expired(&self) {
+        if self.config.idle_timeout.is_none() && self.config.max_lifetime.is_none() {
+            return;
+        }
+        for _ in 0..self.queue.len() {
+            let Some(item) = self.queue.pop() else {
+                break;
+            };
+            if self.is_expired(&item) || self.is_worn_out(&item) {
+                self.free(item);
+            } else {
+                let _ = self.queue.push(item);
+            }
+        }
+    }
+
+    /// Check whether an item should be destroyed instead of being reused,
+    /// whatever the number of allocated items.
+    pub(crate) fn is_worn_out(&self, item: &Prc<T>) -> bool {
+        let max_uses = self.config.max_uses;
+        !item.is_uninit()
+            && (max_uses > 0 && item.use_count() >= max_uses
+                || self
+                    .config
+                    .max_lifetime
+                    .is_some_and(|lifetime| item.age() > lifetime))
+    }
+
+    /// Check whether an idle item should be freed instead of being reused.
+    fn is_expired(&self, item: &Prc<T>) -> bool {
+        self.config
+            .idle_timeout
+            .is_some_and(|timeout| item.idle_for() > timeout)
+            && self.allocated.load(Acquire) > self.config.prealloc
+    }
+
+    /// Pop an idle item from the queue, backing off according to the configured strategy.
+    fn pop_with_backoff(&self) -> Option<Prc<T>> {
+        let mut step = 0;
+        loop {
+            match self.pop_idle() {
+                Some(item) => return Some(item),
+                None if self.config.backoff.snooze(step) => step += 1,
+                None => return None,
+            }
+        }
+    }
+
+    /// Apply `func` to a freshly pulled item.
+    ///
+    /// If `func` panics, the item is left to be recycled if a clear function is
+    /// configured. Otherwise, it is in an unknown state and is discarded, so that
+    /// it is never reused.
+    pub(crate) fn guarded<F, R>(&self, item: &mut Option<Prc<T>>, func: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            func(unsafe { Prc::get_mut_unchecked(item.as_mut().unwrap()) })
+        }));
+        match result {
+            Ok(result) => result,
+            Err(payload) => {
+                if (!self.config.has_clear() || self.config.clear_policy == ClearPolicy::Never)
+                    && let Some(item) = item.take()
+                {
+                    self.discard(item);
+                }
+                resume_unwind(payload)
             }
         }
     }
 
+    /// Destroy an item instead of recycling it, and free its slot in the pool.
+    pub(crate) fn discard(&self, item: Prc<T>) {
+        #[cfg(feature = "instrument")]
+        self.untrack(&item);
+        let pooled = item.is_pooled();
+        unsafe { item.drop_slow() };
+        if pooled {
+            self.allocated.fetch_sub(1, Release);
+            self.notify.notify_waiters();
+        }
+    }
+
+    /// Account a temporary allocation beyond the capacity if the policy is
+    /// [`ExhaustedPolicy::Grow`].
+    fn try_grow(&self) -> bool {
+        let ExhaustedPolicy::Grow { max } = self.config.on_exhausted else {
+            return false;
+        };
+        self.allocated
+            .fetch_update(AcqRel, Acquire, |current| {
+                (current < max).then_some(current + 1)
+            })
+            .is_ok()
+    }
+
+    /// Check whether more items than the soft capacity, or than the capacity
+    /// after growing, are allocated, so that an item being recycled should be
+    /// freed instead. Concurrent recycles may free a few more items, which are
+    /// allocated again on demand.
+    fn is_over_soft_capacity(&self) -> bool {
+        let limit = match self.config.soft_capacity {
+            0 => self.config.capacity,
+            soft => soft,
+        };
+        self.allocated.load(Acquire) > limit
+    }
+
+    /// Move the value out of an item, and free its slot in the pool.
+    pub(crate) fn detach(&self, item: Prc<T>) -> T {
+        #[cfg(feature = "instrument")]
+        self.untrack(&item);
+        let pooled = item.is_pooled();
+        let value = unsafe { item.into_inner() };
+        if pooled {
+            self.allocated.fetch_sub(1, Release);
+            self.notify.notify_waiters();
+        }
+        value
+    }
+
+    /// Keep one reference of an item forever so it is never recycled, and free
+    /// its slot in the pool the first time it is leaked.
+    pub(crate) fn leak(&self, item: Prc<T>) -> &'static T
+    where
+        T: 'static,
+    {
+        #[cfg(feature = "instrument")]
+        self.untrack(&item);
+        if item.mark_leaked() && item.is_pooled() {
+            self.allocated.fetch_sub(1, Release);
+            self.notify.notify_waiters();
+        }
+        item.leak()
+    }
+
     /// Reclaim an item from the pool to reduce memory usage.
     fn reclaim(&self) {
-        if let Some(item) = self.queue.pop() {
-            unsafe { item.drop_slow() };
-            let current = self.allocated.fetch_sub(1, Release) - 1;
-            if self.config.need_process_reclamation && current <= self.config.prealloc {
-                if self.additional_allocated.load(Relaxed) {
-                    self.additional_allocated.store(false, Relaxed);
+        for _ in 0..max(self.config.reclaim_batch, 1) {
+            if self.queue.len() <= self.config.min_idle
+                || self.allocated.load(Acquire) <= self.config.prealloc
+                || !self.free_idle_item()
+            {
+                break;
+            }
+        }
+    }
+
+    /// Free idle items until `idle` are left, keeping preallocated items.
+    fn reclaim_to(&self, idle: usize) {
+        let idle = max(idle, self.config.min_idle);
+        while self.queue.len() > idle
+            && self.allocated.load(Acquire) > self.config.prealloc
+            && self.free_idle_item()
+        {}
+    }
+
+    /// Free the least recently used idle item. Return `false` if there is no
+    /// idle item.
+    fn free_idle_item(&self) -> bool {
+        let Some(item) = self.queue.pop() else {
+            return false;
+        };
+        self.free(item);
+        true
+    }
+
+    /// Free an idle item taken from the queue, or an item being recycled
+    /// which is no longer tracked.
+    fn free(&self, item: Prc<T>) {
+        unsafe { item.drop_slow() };
+        let current = self.allocated.fetch_sub(1, Release) - 1;
+        if current <= self.config.prealloc && self.additional_allocated.load(Relaxed) {
+            self.additional_allocated.store(false, Relaxed);
+        }
+    }
+
+    /// Apply the configured clear function to an item.
+    fn clear_item(&self, item: &mut T) {
+        if let Some(func) = &self.config.clear_func {
+            func(item)
+        }
+        if let Some(func) = &self.config.clear_with {
+            func(item)
+        }
+    }
+
+    /// Release a reference to an item, recycling it if this was the last one.
+    pub(crate) fn release_ref(&self, item: Prc<T>) {
+        let count = item.dec_ref();
+        #[cfg(feature = "paranoid")]
+        assert_ne!(
+            count,
+            0,
+            "reference count underflow of slot {} in pool `{}`",
+            item.slot(),
+            self.queue.name()
+        );
+        if count == 1 {
+            // This was the last reference, return to the pool.
+            fence(Acquire);
+            self.recycle(item);
+        }
+    }
+
+    /// Pull a fresh item holding a clone of `src`, falling back to an unpooled
+    /// clone if the pool is exhausted.
+    pub(crate) fn pull_clone_of(&self, src: &T) -> Prc<T>
+    where
+        T: Clone,
+    {
+        match self.pull_inner(false) {
+            Ok(mut item) => {
+                unsafe { Prc::get_mut_unchecked(&mut item) }.clone_from(src);
+                item
+            }
+            Err(_) => Prc::new_unpooled(src.clone(), self.new_metadata()),
+        }
+    }
+
+    /// Apply the clear function to an item being recycled, according to the
+    /// clear policy. The item must not be shared.
+    pub(crate) fn clear_recycled(&self, item: &mut Prc<T>) {
+        match self.config.clear_policy {
+            ClearPolicy::OnRecycle => {
+                if !item.take_skip_clear() {
+                    self.clear_item(unsafe { Prc::get_mut_unchecked(item) });
                 }
             }
+            // The mark to skip the clear function is kept until the item is
+            // pulled again.
+            ClearPolicy::OnPull => {}
+            ClearPolicy::Never => {
+                item.take_skip_clear();
+            }
+        }
+    }
+
+    /// Clear an item being recycled and apply the recycle hook.
+    fn recycle_decision(&self, item: &mut Prc<T>) -> RecycleDecision {
+        self.clear_recycled(item);
+        match self.config.on_recycle {
+            Some(func) => func(unsafe { Prc::get_mut_unchecked(item) }),
+            None => RecycleDecision::Reuse,
         }
     }
 
     /// Recycle an item back into the pool.
     pub(crate) fn recycle(&self, mut item: Prc<T>) {
-        if let Some(func) = &self.config.clear_func {
-            func(unsafe { Prc::get_mut_unchecked(&mut item) })
+        #[cfg(feature = "instrument")]
+        self.untrack(&item);
+        #[cfg(feature = "paranoid")]
+        if item.is_pooled() {
+            self.queue.check_owner(&item);
+        }
+        if !item.is_pooled() || item.is_discarded() || self.is_closed() || self.is_worn_out(&item) {
+            self.discard(item);
+            return;
+        }
+        if self.is_over_soft_capacity() {
+            self.free(item);
+            self.notify.notify_waiters();
+            return;
+        }
+        if item.take_pinned() {
+            // Drop the pinned value in place before its memory is reused.
+            match self.new_value() {
+                Ok(value) => *unsafe { Prc::get_mut_unchecked(&mut item) } = value,
+                Err(_) => {
+                    self.discard(item);
+                    return;
+                }
+            }
+        } else {
+            // A panic leaves the item in an unknown state, so it is destroyed
+            // and its slot freed rather than leaked.
+            let decision = catch_unwind(AssertUnwindSafe(|| self.recycle_decision(&mut item)));
+            match decision {
+                Ok(RecycleDecision::Reuse) => {}
+                Ok(RecycleDecision::Discard) => {
+                    self.discard(item);
+                    return;
+                }
+                Ok(RecycleDecision::Quarantine) => {
+                    self.lock_quarantined().0.push(item);
+                    return;
+                }
+                Err(payload) => {
+                    self.discard(item);
+                    if self.config.propagate_clear_panics {
+                        resume_unwind(payload);
+                    }
+                    return;
+                }
+            }
+        }
+        item.next_generation();
+        if self.config.idle_timeout.is_some() {
+            item.mark_recycled();
+        }
+        if self.config.quarantine.is_some() {
+            let mut held_back = self.lock_held_back();
+            let pulls = held_back.pulls;
+            held_back.items.push_back((item, pulls, Instant::now()));
+            drop(held_back);
+            // The pool may have been closed since it was checked above.
+            if self.is_closed() {
+                let held_back = std::mem::take(&mut self.lock_held_back().items);
+                for (item, _, _) in held_back {
+                    self.discard(item);
+                }
+            }
+            // An exhausted pool reuses held back items early.
+            self.notify.notify_waiters();
+            return;
         }
+        let idle = self.queue.len();
         if self.queue.push(item).is_err() {
-            panic!("It is imposible that the pool is full when recycling an item");
+            panic!(
+                "It is imposible that the pool `{}` is full when recycling an item",
+                self.config.display_name()
+            );
+        }
+        // The pool may have been closed since it was checked above, after
+        // draining the queue.
+        fence(SeqCst);
+        if self.is_closed() {
+            while let Some(item) = self.queue.pop() {
+                self.discard(item);
+            }
+        }
+        self.notify.notify_waiters();
+        let high = self.config.idle_high_watermark;
+        if high > 0 && idle >= high && self.reclaim.enabled.load(Relaxed) {
+            self.reclaim_to(self.config.idle_low_watermark);
         }
     }
 }
 
-/// Configuration for the pool.
-#[derive(Debug)]
-pub struct Config<T: Default> {
+#[cfg(feature = "serde")]
+impl<'a, 'de, T: serde::Deserialize<'de>> serde::de::DeserializeSeed<'de> for &'a Pool<T> {
+    type Value = Entry<'a, T>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut entry = self.pull().map_err(serde::de::Error::custom)?;
+        let item = unsafe { entry.get_mut_unchecked() };
+        T::deserialize_in_place(deserializer, item)?;
+        Ok(entry)
+    }
+}
+
+/// Where and when an item still in use was pulled, see [`Pool::outstanding`].
+#[cfg(feature = "instrument")]
+#[derive(Debug, Clone, Copy)]
+pub struct PullRecord {
+    location: &'static std::panic::Location<'static>,
+    pulled_at: Instant,
+}
+
+#[cfg(feature = "instrument")]
+impl PullRecord {
+    fn new(location: &'static std::panic::Location<'static>) -> Self {
+        Self {
+            location,
+            pulled_at: Instant::now(),
+        }
+    }
+
+    /// Get the call site which pulled the item.
+    pub fn location(&self) -> &'static std::panic::Location<'static> {
+        self.location
+    }
+
+    /// Get when the item was pulled.
+    pub fn pulled_at(&self) -> Instant {
+        self.pulled_at
+    }
+
+    /// Get the time elapsed since the item was pulled.
+    pub fn held_for(&self) -> Duration {
+        self.pulled_at.elapsed()
+    }
+}
+
+/// Function reporting an item held for too long, called with the pool name, see
+/// [`Builder::max_hold_warning`](crate::Builder::max_hold_warning).
+#[cfg(feature = "instrument")]
+pub type HoldWarningFn = fn(&str, &PullRecord);
+
+/// Whether a pulled item was reused from the pool or freshly allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PullKind {
+    /// The item was idle in the pool and has been reused.
+    Reused,
+    /// The pool had no idle item and a new one has been allocated.
+    FreshlyAllocated,
+}
+
+/// What a pull does when all items are in use and the capacity is reached.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Builder, ExhaustedPolicy};
+///
+/// let pool = Builder::<u32>::new()
+///     .capacity(1)
+///     .on_exhausted(ExhaustedPolicy::Grow { max: 2 })
+///     .build();
+/// let item1 = pool.pull().unwrap();
+/// let item2 = pool.pull().unwrap();
+/// assert!(pool.pull().is_err());
+/// drop(item2);
+/// // Items allocated beyond the capacity are freed when recycled.
+/// assert_eq!(pool.allocated(), 1);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ExhaustedPolicy {
+    /// Return [`PullError::Exhausted`] right away.
+    #[default]
+    ReturnNone,
+    /// Block until an item is recycled.
+    Block,
+    /// Allocate temporary items beyond the capacity, up to `max` items in
+    /// total. They are freed instead of being recycled.
+    Grow {
+        /// Maximum number of allocated items.
+        max: usize,
+    },
+}
+
+/// What happens to an item returned to the pool, as decided by the hook set
+/// with [`Builder::on_recycle`](crate::Builder::on_recycle).
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Builder, RecycleDecision};
+///
+/// let pool = Builder::<Vec<u8>>::new()
+///     .capacity(2)
+///     .on_recycle(|buf| match buf.capacity() {
+///         0..=1024 => {
+///             buf.clear();
+///             RecycleDecision::Reuse
+///         }
+///         _ => RecycleDecision::Discard,
+///     })
+///     .build();
+/// let mut buf = pool.pull().unwrap();
+/// buf.resize(4096, 0);
+/// drop(buf);
+/// assert_eq!(pool.allocated(), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecycleDecision {
+    /// Return the item to the pool to be reused.
+    Reuse,
+    /// Destroy the item and free its slot.
+    Discard,
+    /// Set the item aside for inspection, keeping its slot allocated until it
+    /// is taken with [`Pool::take_quarantined`].
+    Quarantine,
+}
+
+/// How long recycled items are held back before they can be reused, see
+/// [`Builder::quarantine`](crate::Builder::quarantine).
+///
+/// Delaying reuse makes code still using an item after returning it observe
+/// stale or cleared data, instead of racing with the next user of the item.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Builder, QuarantineWindow};
+///
+/// let pool = Builder::<u32>::new()
+///     .capacity(4)
+///     .quarantine(QuarantineWindow::Pulls(2))
+///     .build();
+/// *pool.pull().unwrap() = 1;
+/// // The recycled item is held back for the next 2 pulls.
+/// assert_eq!(*pool.pull().unwrap(), 0);
+/// assert_eq!(*pool.pull().unwrap(), 0);
+/// assert_eq!(*pool.pull().unwrap(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum QuarantineWindow {
+    /// Hold items back for this number of subsequent pulls.
+    Pulls(usize),
+    /// Hold items back for this duration.
+    Duration(Duration),
+}
+
+/// When the clear function of a pool is applied to its items.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Builder, ClearPolicy};
+///
+/// let pool = Builder::<String>::new()
+///     .capacity(1)
+///     .clear_func(String::clear)
+///     .clear_policy(ClearPolicy::OnPull)
+///     .build();
+/// drop(pool.pull_with(|s| s.push_str("hello")).unwrap());
+/// assert_eq!(&*pool.pull().unwrap(), "");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ClearPolicy {
+    /// Clear items when they are recycled, so the cost is paid by the side
+    /// dropping the last reference.
+    #[default]
+    OnRecycle,
+    /// Clear items when they are pulled again, so the cost is paid by the side
+    /// pulling them. Freshly allocated items are not cleared.
+    OnPull,
+    /// Never apply the clear function, for items which are always overwritten
+    /// by the next user.
+    Never,
+}
+
+/// How the preallocated items of a pool are created.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Builder, PreallocMode};
+///
+/// let pool = Builder::<Vec<u8>>::new()
+///     .capacity(100)
+///     .prealloc(100)
+///     .prealloc_mode(PreallocMode::Lazy { chunk: 10 })
+///     .build();
+/// assert_eq!(pool.allocated(), 0);
+/// let item = pool.pull().unwrap();
+/// assert_eq!(pool.allocated(), 10);
+/// assert_eq!(pool.available_noalloc(), 9);
+/// ```
+///
+/// With [`PreallocMode::Deferred`], the slots are allocated up front but each
+/// item is constructed the first time its slot is pulled:
+///
+/// ```rust
+/// use concurrent_pool::{Builder, PreallocMode};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static CREATED: AtomicUsize = AtomicUsize::new(0);
+///
+/// let pool = Builder::from_factory(|| CREATED.fetch_add(1, Ordering::Relaxed))
+///     .capacity(100)
+///     .prealloc(100)
+///     .prealloc_mode(PreallocMode::Deferred)
+///     .build();
+/// assert_eq!(pool.allocated(), 100);
+/// assert_eq!(CREATED.load(Ordering::Relaxed), 0);
+/// let item = pool.pull().unwrap();
+/// assert_eq!(CREATED.load(Ordering::Relaxed), 1);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PreallocMode {
+    /// Create all preallocated items when the pool is built.
+    #[default]
+    Eager,
+    /// Create preallocated items `chunk` at a time, when a pull finds no idle
+    /// item, to avoid stalling the startup of large pools.
+    Lazy {
+        /// Number of items created at once.
+        chunk: usize,
+    },
+    /// Allocate the slots of all preallocated items when the pool is built, but
+    /// construct each item the first time its slot is pulled.
+    Deferred,
+}
+
+/// Effective configuration of a pool, returned by [`Pool::config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConfigView {
     /// Maximum capacity of the pool.
     pub capacity: usize,
+    /// Number of preallocated items.
+    pub prealloc: usize,
+    /// Whether allocated items are automatically reclaimed.
+    pub auto_reclaim: bool,
+    /// Threshold of `surplus-pull` continuous occurrence to trigger reclamation.
+    pub surpluspull_threshold_for_reclaim: usize,
+    /// Threshold for idle items to judge as a `surplus-pull`.
+    pub idle_threshold_for_surpluspull: usize,
+    /// Strategy to back off when the pool appears empty under contention.
+    pub backoff: BackoffStrategy,
+    /// When the clear function is applied.
+    pub clear_policy: ClearPolicy,
+    /// How the preallocated items are created.
+    pub prealloc_mode: PreallocMode,
+    /// Minimum number of idle items kept in the pool.
+    pub min_idle: usize,
+    /// Number of allocated items above which recycled items are freed, or 0
+    /// if there is no soft limit.
+    pub soft_capacity: usize,
+    /// What a pull does when the capacity is reached.
+    pub on_exhausted: ExhaustedPolicy,
+    /// Maximum number of idle items freed each time reclamation is triggered.
+    pub reclaim_batch: usize,
+    /// Number of idle items above which reclamation starts, or 0 if disabled.
+    pub idle_high_watermark: usize,
+    /// Number of idle items at which reclamation stops.
+    pub idle_low_watermark: usize,
+    /// Duration after which an idle item beyond `prealloc` is freed.
+    pub idle_timeout: Option<Duration>,
+    /// Age after which an item is destroyed instead of being reused.
+    pub max_lifetime: Option<Duration>,
+    /// Number of pulls after which an item is destroyed, or 0 if there is no
+    /// limit.
+    pub max_uses: usize,
+    /// Whether panics of the clear function are resumed when recycling.
+    pub propagate_clear_panics: bool,
+    /// Window during which recycled items are held back before reuse.
+    pub quarantine: Option<QuarantineWindow>,
+}
+
+/// Function to create new items of a pool.
+pub(crate) enum Factory<T> {
+    /// `T::default`, kept as a function pointer so that `T` needs not be
+    /// `'static`.
+    Default(fn() -> T),
+    /// Function set with [`Builder::factory`](crate::Builder::factory).
+    Func(Arc<dyn Fn() -> T + Send + Sync>),
+    /// Function set with [`Builder::try_factory`](crate::Builder::try_factory).
+    TryFunc(Arc<dyn Fn() -> Result<T, FactoryError> + Send + Sync>),
+}
+
+impl<T> Factory<T> {
+    fn create(&self) -> Result<T, FactoryError> {
+        match self {
+            Self::Default(func) => Ok(func()),
+            Self::Func(func) => Ok(func()),
+            Self::TryFunc(func) => func(),
+        }
+    }
+}
+
+impl<T> Clone for Factory<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Default(func) => Self::Default(*func),
+            Self::Func(func) => Self::Func(func.clone()),
+            Self::TryFunc(func) => Self::TryFunc(func.clone()),
+        }
+    }
+}
+
+/// Closure to clear or reset an item, see [`Config::clear_with`].
+pub type ClearFn<T> = Arc<dyn Fn(&mut T) + Send + Sync>;
+
+/// Configuration for the pool.
+pub struct Config<T> {
+    /// Function to create new items.
+    pub(crate) factory: Factory<T>,
+    /// Optional name of the pool, shown in its `Debug` output and panic
+    /// messages to tell pools apart.
+    pub name: Option<String>,
+    /// Maximum capacity of the pool, pulls fail beyond it.
+    pub capacity: usize,
+    /// Soft limit of allocated items, or 0 to disable it. Pulls beyond it still
+    /// allocate up to `capacity`, but recycled items are freed instead of being
+    /// kept idle until the pool shrinks back to the soft limit.
+    pub soft_capacity: usize,
+    /// What a pull does when all items are in use and the capacity is reached.
+    pub on_exhausted: ExhaustedPolicy,
     /// Number of items to preallocate.
     pub prealloc: usize,
+    /// How the preallocated items are created.
+    pub prealloc_mode: PreallocMode,
+    /// Minimum number of idle items. When a pull leaves fewer idle items, the
+    /// pool allocates replacements right away as long as the capacity allows,
+    /// and reclamation never frees items below it.
+    pub min_idle: usize,
     /// Whether to automatically reclaim allocated items and free them to reduce memory usage.
     pub auto_reclaim: bool,
     /// Threshold of `surplus-pull` continuous occurrence to trigger reclamation
@@ -445,27 +3264,384 @@ pub struct Config<T: Default> {
     pub surpluspull_threshold_for_reclaim: usize,
     /// Threshold for idle items to judge as a surplus-pull when `auto_reclaim` is enabled.
     pub idle_threshold_for_surpluspull: usize,
+    /// Maximum number of idle items freed each time reclamation is triggered.
+    /// Reclamation never frees preallocated items.
+    pub reclaim_batch: usize,
+    /// Number of idle items above which reclamation frees idle items down to
+    /// `idle_low_watermark` when `auto_reclaim` is enabled, instead of counting
+    /// `surplus-pull`s. Disabled if 0.
+    pub idle_high_watermark: usize,
+    /// Number of idle items at which reclamation started by `idle_high_watermark`
+    /// stops.
+    pub idle_low_watermark: usize,
+    /// Optional duration after which an idle item beyond `prealloc` is freed
+    /// instead of being reused. Expired items are freed when they are found by a
+    /// pull.
+    pub idle_timeout: Option<Duration>,
+    /// Optional age after which an item is destroyed instead of being reused, its
+    /// slot being filled with a new item on demand. The age is checked when the
+    /// item is recycled or pulled.
+    pub max_lifetime: Option<Duration>,
+    /// Number of pulls after which an item is destroyed when it is recycled,
+    /// its slot being filled with a new item on demand, or 0 to reuse items
+    /// indefinitely.
+    pub max_uses: usize,
+    /// Whether a panic of the clear function or of the recycle hook is resumed
+    /// after the item is destroyed. Otherwise, the panic is swallowed.
+    pub propagate_clear_panics: bool,
+    /// Optional window during which recycled items are held back before they can
+    /// be reused, to debug code using items after returning them. Held items are
+    /// counted as in use, and are only reused early if the pool is exhausted.
+    pub quarantine: Option<QuarantineWindow>,
     /// Optional function to clear or reset an item before it is reused.
     pub clear_func: Option<fn(&mut T)>,
+    /// Optional closure to clear or reset an item before it is reused, which
+    /// can capture state. Applied after `clear_func` if both are set.
+    pub clear_with: Option<ClearFn<T>>,
+    /// Optional function applied to an item each time it is pulled.
+    pub before_pull: Option<fn(&mut T)>,
+    /// Optional function called with the pool name and the deadline of a lease
+    /// when it is revoked, see [`Pool::pull_leased`].
+    pub on_lease_revoked: Option<fn(&str, Instant)>,
+    /// Optional function applied to an item when it is recycled, after the
+    /// clear function, deciding whether it is reused.
+    pub on_recycle: Option<fn(&mut T) -> RecycleDecision>,
+    /// When the clear function is applied.
+    pub clear_policy: ClearPolicy,
+    /// Strategy to back off when the pool appears empty under contention.
+    pub backoff: BackoffStrategy,
+    /// Optional function to create the metadata attached to each item.
+    pub metadata: Option<fn() -> Box<dyn Any + Send + Sync>>,
+    /// Optional function reporting whether the process is under memory
+    /// pressure, checked by [`Pool::maintain`] to free idle items.
+    pub memory_pressure: Option<fn() -> bool>,
+    /// Optional duration after which an item in use is reported by
+    /// [`Pool::maintain`] to the function, called with the pool name and
+    /// where the item was pulled.
+    #[cfg(feature = "instrument")]
+    pub max_hold_warning: Option<(Duration, HoldWarningFn)>,
     /// Internal flag to indicate if the pool needs to process reclamation.
     need_process_reclamation: bool,
 }
 
 impl<T: Default> Default for Config<T> {
     fn default() -> Self {
+        Self::from_factory(Factory::Default(T::default))
+    }
+}
+
+/// Deserializable fields of [`Config`], the functions are left to their
+/// defaults.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFields {
+    name: Option<String>,
+    capacity: usize,
+    soft_capacity: usize,
+    on_exhausted: ExhaustedPolicy,
+    prealloc: usize,
+    prealloc_mode: PreallocMode,
+    min_idle: usize,
+    auto_reclaim: bool,
+    surpluspull_threshold_for_reclaim: usize,
+    idle_threshold_for_surpluspull: usize,
+    reclaim_batch: usize,
+    idle_high_watermark: usize,
+    idle_low_watermark: usize,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
+    max_uses: usize,
+    propagate_clear_panics: bool,
+    quarantine: Option<QuarantineWindow>,
+    backoff: BackoffStrategy,
+    clear_policy: ClearPolicy,
+}
+
+#[cfg(feature = "serde")]
+impl Default for ConfigFields {
+    fn default() -> Self {
+        let config = Config::<()>::default();
+        Self {
+            name: config.name,
+            capacity: config.capacity,
+            soft_capacity: config.soft_capacity,
+            on_exhausted: config.on_exhausted,
+            prealloc: config.prealloc,
+            prealloc_mode: config.prealloc_mode,
+            min_idle: config.min_idle,
+            auto_reclaim: config.auto_reclaim,
+            surpluspull_threshold_for_reclaim: config.surpluspull_threshold_for_reclaim,
+            idle_threshold_for_surpluspull: config.idle_threshold_for_surpluspull,
+            reclaim_batch: config.reclaim_batch,
+            idle_high_watermark: config.idle_high_watermark,
+            idle_low_watermark: config.idle_low_watermark,
+            idle_timeout: config.idle_timeout,
+            max_lifetime: config.max_lifetime,
+            max_uses: config.max_uses,
+            propagate_clear_panics: config.propagate_clear_panics,
+            quarantine: config.quarantine,
+            backoff: config.backoff,
+            clear_policy: config.clear_policy,
+        }
+    }
+}
+
+/// Deserialize the numeric and enum fields of the configuration, e.g. from a
+/// deployment config. Missing fields keep their default values, while the
+/// functions such as `clear_func` are always left unset.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{BackoffStrategy, Config, Pool};
+///
+/// let config: Config<String> =
+///     serde_json::from_str(r#"{ "capacity": 8, "prealloc": 2, "backoff": "spin" }"#).unwrap();
+/// assert_eq!(config.backoff, BackoffStrategy::Spin);
+/// let pool = Pool::with_config(config);
+/// assert_eq!(pool.capacity(), 8);
+/// ```
+#[cfg(feature = "serde")]
+impl<'de, T: Default> serde::Deserialize<'de> for Config<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let fields = ConfigFields::deserialize(deserializer)?;
+        Ok(Self {
+            name: fields.name,
+            capacity: fields.capacity,
+            soft_capacity: fields.soft_capacity,
+            on_exhausted: fields.on_exhausted,
+            prealloc: fields.prealloc,
+            prealloc_mode: fields.prealloc_mode,
+            min_idle: fields.min_idle,
+            auto_reclaim: fields.auto_reclaim,
+            surpluspull_threshold_for_reclaim: fields.surpluspull_threshold_for_reclaim,
+            idle_threshold_for_surpluspull: fields.idle_threshold_for_surpluspull,
+            reclaim_batch: fields.reclaim_batch,
+            idle_high_watermark: fields.idle_high_watermark,
+            idle_low_watermark: fields.idle_low_watermark,
+            idle_timeout: fields.idle_timeout,
+            max_lifetime: fields.max_lifetime,
+            max_uses: fields.max_uses,
+            propagate_clear_panics: fields.propagate_clear_panics,
+            quarantine: fields.quarantine,
+            backoff: fields.backoff,
+            clear_policy: fields.clear_policy,
+            ..Self::default()
+        })
+    }
+}
+
+impl<T> Clone for Config<T> {
+    fn clone(&self) -> Self {
+        Self {
+            factory: self.factory.clone(),
+            name: self.name.clone(),
+            capacity: self.capacity,
+            soft_capacity: self.soft_capacity,
+            on_exhausted: self.on_exhausted,
+            prealloc: self.prealloc,
+            prealloc_mode: self.prealloc_mode,
+            min_idle: self.min_idle,
+            auto_reclaim: self.auto_reclaim,
+            surpluspull_threshold_for_reclaim: self.surpluspull_threshold_for_reclaim,
+            idle_threshold_for_surpluspull: self.idle_threshold_for_surpluspull,
+            reclaim_batch: self.reclaim_batch,
+            idle_high_watermark: self.idle_high_watermark,
+            idle_low_watermark: self.idle_low_watermark,
+            idle_timeout: self.idle_timeout,
+            max_lifetime: self.max_lifetime,
+            max_uses: self.max_uses,
+            propagate_clear_panics: self.propagate_clear_panics,
+            quarantine: self.quarantine,
+            clear_func: self.clear_func,
+            clear_with: self.clear_with.clone(),
+            before_pull: self.before_pull,
+            on_recycle: self.on_recycle,
+            on_lease_revoked: self.on_lease_revoked,
+            clear_policy: self.clear_policy,
+            backoff: self.backoff,
+            metadata: self.metadata,
+            memory_pressure: self.memory_pressure,
+            #[cfg(feature = "instrument")]
+            max_hold_warning: self.max_hold_warning,
+            need_process_reclamation: self.need_process_reclamation,
+        }
+    }
+}
+
+impl<T> Debug for Config<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("name", &self.name)
+            .field("capacity", &self.capacity)
+            .field("soft_capacity", &self.soft_capacity)
+            .field("on_exhausted", &self.on_exhausted)
+            .field("prealloc", &self.prealloc)
+            .field("prealloc_mode", &self.prealloc_mode)
+            .field("min_idle", &self.min_idle)
+            .field("auto_reclaim", &self.auto_reclaim)
+            .field(
+                "surpluspull_threshold_for_reclaim",
+                &self.surpluspull_threshold_for_reclaim,
+            )
+            .field(
+                "idle_threshold_for_surpluspull",
+                &self.idle_threshold_for_surpluspull,
+            )
+            .field("reclaim_batch", &self.reclaim_batch)
+            .field("idle_high_watermark", &self.idle_high_watermark)
+            .field("idle_low_watermark", &self.idle_low_watermark)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("max_lifetime", &self.max_lifetime)
+            .field("max_uses", &self.max_uses)
+            .field("propagate_clear_panics", &self.propagate_clear_panics)
+            .field("quarantine", &self.quarantine)
+            .field("clear_func", &self.clear_func)
+            .field("clear_with", &self.clear_with.is_some())
+            .field("before_pull", &self.before_pull)
+            .field("on_recycle", &self.on_recycle)
+            .field("on_lease_revoked", &self.on_lease_revoked)
+            .field("clear_policy", &self.clear_policy)
+            .field("backoff", &self.backoff)
+            .field("metadata", &self.metadata)
+            .field("memory_pressure", &self.memory_pressure)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Config<T> {
+    /// Create a default configuration creating new items with `factory`, for
+    /// items which do not implement [`Default`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Config, Pool};
+    ///
+    /// let mut config = Config::with_factory(|| Vec::<u8>::with_capacity(64));
+    /// config.capacity = 1;
+    /// let pool = Pool::with_config(config);
+    /// assert!(pool.pull().unwrap().capacity() >= 64);
+    /// ```
+    pub fn with_factory<F>(factory: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Self::from_factory(Factory::Func(Arc::new(factory)))
+    }
+
+    pub(crate) fn from_factory(factory: Factory<T>) -> Self {
         Self {
+            factory,
+            name: None,
             capacity: 1024,
+            soft_capacity: 0,
+            on_exhausted: ExhaustedPolicy::ReturnNone,
             prealloc: 0,
+            prealloc_mode: PreallocMode::Eager,
+            min_idle: 0,
             auto_reclaim: false,
             clear_func: None,
+            clear_with: None,
+            before_pull: None,
+            on_recycle: None,
+            on_lease_revoked: None,
+            clear_policy: ClearPolicy::OnRecycle,
+            backoff: BackoffStrategy::None,
+            metadata: None,
+            memory_pressure: None,
+            #[cfg(feature = "instrument")]
+            max_hold_warning: None,
             surpluspull_threshold_for_reclaim: 0,
             idle_threshold_for_surpluspull: 0,
+            reclaim_batch: 1,
+            idle_high_watermark: 0,
+            idle_low_watermark: 0,
+            idle_timeout: None,
+            max_lifetime: None,
+            max_uses: 0,
+            propagate_clear_panics: true,
+            quarantine: None,
             need_process_reclamation: false,
         }
     }
-}
 
-impl<T: Default> Config<T> {
+    /// Check that the configuration is consistent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concurrent_pool::{Config, ConfigError};
+    ///
+    /// let mut config = Config::<u32>::default();
+    /// config.capacity = 1;
+    /// config.prealloc = 2;
+    /// assert_eq!(
+    ///     config.validate(),
+    ///     Err(ConfigError::PreallocExceedsCapacity { prealloc: 2, capacity: 1 })
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.validate_with_seed(0)
+    }
+
+    /// Check that the configuration is consistent with `seeded` items to start
+    /// the pool with.
+    pub(crate) fn validate_with_seed(&self, seeded: usize) -> Result<(), ConfigError> {
+        let prealloc = max(self.prealloc, seeded);
+        if self.capacity == 0 && prealloc > 0 {
+            return Err(ConfigError::ZeroCapacityWithPrealloc { prealloc });
+        }
+        if prealloc > self.capacity {
+            return Err(ConfigError::PreallocExceedsCapacity {
+                prealloc,
+                capacity: self.capacity,
+            });
+        }
+        if self.idle_threshold_for_surpluspull > self.capacity {
+            return Err(ConfigError::IdleThresholdExceedsCapacity {
+                threshold: self.idle_threshold_for_surpluspull,
+                capacity: self.capacity,
+            });
+        }
+        if self.soft_capacity > self.capacity {
+            return Err(ConfigError::SoftCapacityExceedsCapacity {
+                soft_capacity: self.soft_capacity,
+                capacity: self.capacity,
+            });
+        }
+        if self.idle_low_watermark > self.idle_high_watermark
+            || self.idle_high_watermark > self.capacity
+        {
+            return Err(ConfigError::InvalidWatermarks {
+                low: self.idle_low_watermark,
+                high: self.idle_high_watermark,
+                capacity: self.capacity,
+            });
+        }
+        if self.min_idle > self.capacity {
+            return Err(ConfigError::MinIdleExceedsCapacity {
+                min_idle: self.min_idle,
+                capacity: self.capacity,
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the name of the pool to show in diagnostics.
+    pub(crate) fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("<unnamed>")
+    }
+
+    /// Check whether a clear function or closure is configured.
+    pub(crate) fn has_clear(&self) -> bool {
+        self.clear_func.is_some() || self.clear_with.is_some()
+    }
+
     pub(crate) fn post_process(&mut self) {
         if self.idle_threshold_for_surpluspull == 0 {
             self.idle_threshold_for_surpluspull = max(1, self.capacity / 20);
@@ -475,10 +3651,21 @@ impl<T: Default> Config<T> {
             self.surpluspull_threshold_for_reclaim = max(2, self.capacity / 100);
         }
 
-        if self.auto_reclaim && self.prealloc != self.capacity {
-            self.need_process_reclamation = true;
-        } else {
-            self.need_process_reclamation = false;
-        }
+        self.need_process_reclamation = self.auto_reclaim && self.prealloc != self.capacity;
+    }
+}
+
+#[cfg(all(test, feature = "paranoid"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "reference count underflow of slot 0 in pool `test`")]
+    fn release_ref_underflow_panics() {
+        let pool = Builder::<u32>::new().capacity(1).name("test").build();
+        let item = pool.pull_inner(false).unwrap();
+        // The reference is released behind the pool's back.
+        item.dec_ref();
+        pool.release_ref(item);
     }
 }
@@ -0,0 +1,53 @@
+//! Memory pressure probes for [`Builder::memory_pressure`](crate::Builder::memory_pressure).
+
+use std::fs;
+
+/// Share of the last 10 seconds, in percent, during which some tasks stalled
+/// on memory above which the cgroup is considered under pressure.
+pub const CGROUP_PRESSURE_THRESHOLD: f64 = 10.0;
+
+/// Check whether the cgroup of the process is under memory pressure, from the
+/// `some avg10` value of its `memory.pressure` file (cgroup v2, Linux).
+///
+/// Return `false` if the pressure is not available.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Builder, pressure};
+///
+/// let pool = Builder::<Vec<u8>>::new()
+///     .capacity(64)
+///     .memory_pressure(pressure::cgroup_memory_pressure)
+///     .build();
+/// pool.maintain();
+/// ```
+pub fn cgroup_memory_pressure() -> bool {
+    read_some_avg10().is_some_and(|avg10| avg10 > CGROUP_PRESSURE_THRESHOLD)
+}
+
+/// Read the `some avg10` value of the `memory.pressure` file of the cgroup of
+/// the process.
+fn read_some_avg10() -> Option<f64> {
+    let cgroup = fs::read_to_string("/proc/self/cgroup").ok()?;
+    // The cgroup v2 entry has the form `0::/path`.
+    let path = cgroup.lines().find_map(|line| line.strip_prefix("0::"))?;
+    let pressure = fs::read_to_string(format!(
+        "/sys/fs/cgroup{}/memory.pressure",
+        path.trim_end_matches('/')
+    ))
+    .ok()?;
+    parse_some_avg10(&pressure)
+}
+
+/// Parse the `avg10` value of the `some` line of a pressure file, e.g.
+/// `some avg10=1.50 avg60=0.80 avg300=0.20 total=12345`.
+fn parse_some_avg10(pressure: &str) -> Option<f64> {
+    pressure
+        .lines()
+        .find_map(|line| line.strip_prefix("some "))?
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("avg10="))?
+        .parse()
+        .ok()
+}
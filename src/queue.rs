@@ -0,0 +1,182 @@
+#[cfg(not(feature = "simple-backend"))]
+use crossbeam_queue::ArrayQueue;
+#[cfg(feature = "simple-backend")]
+use std::collections::VecDeque;
+#[cfg(feature = "simple-backend")]
+use std::sync::Mutex;
+#[cfg(feature = "paranoid")]
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use crate::entry::Prc;
+
+/// Queue holding the idle items of the pool.
+///
+/// Idle items have no references outside the queue, so moving them between
+/// threads only requires `T: Send`, unlike [`Prc<T>`] which may be shared.
+///
+/// Items are popped in the order they were pushed, i.e. recycled, so the front
+/// of the queue is the least recently used idle item. Reclamation relies on it
+/// to free the least recently used items first.
+///
+/// With the `simple-backend` feature, the lock-free queue is replaced by a
+/// `Mutex<VecDeque>`, a trivially correct reference when bisecting concurrency
+/// bugs. With the `paranoid` feature, pushed items are checked to belong to the
+/// pool and to not be in the queue already.
+#[derive(Debug)]
+pub(crate) struct IdleQueue<T> {
+    #[cfg(not(feature = "simple-backend"))]
+    queue: ArrayQueue<Prc<T>>,
+    #[cfg(feature = "simple-backend")]
+    queue: Mutex<VecDeque<Prc<T>>>,
+    #[cfg(feature = "simple-backend")]
+    cap: usize,
+    #[cfg(feature = "paranoid")]
+    owner: Owner,
+}
+
+unsafe impl<T: Send> Send for IdleQueue<T> {}
+unsafe impl<T: Send> Sync for IdleQueue<T> {}
+
+#[cfg(not(feature = "simple-backend"))]
+impl<T> IdleQueue<T> {
+    pub(crate) fn new(cap: usize) -> Self {
+        Self {
+            queue: ArrayQueue::new(cap),
+            #[cfg(feature = "paranoid")]
+            owner: Owner::new(),
+        }
+    }
+
+    /// Push an item which has no other references.
+    pub(crate) fn push(&self, item: Prc<T>) -> Result<(), Prc<T>> {
+        #[cfg(feature = "paranoid")]
+        self.check_push(&item);
+        self.queue.push(item)
+    }
+
+    pub(crate) fn pop(&self) -> Option<Prc<T>> {
+        let item = self.queue.pop();
+        #[cfg(feature = "paranoid")]
+        item.iter().for_each(Prc::unmark_queued);
+        item
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+#[cfg(feature = "simple-backend")]
+impl<T> IdleQueue<T> {
+    pub(crate) fn new(cap: usize) -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::with_capacity(cap)),
+            cap,
+            #[cfg(feature = "paranoid")]
+            owner: Owner::new(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<Prc<T>>> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Push an item which has no other references.
+    pub(crate) fn push(&self, item: Prc<T>) -> Result<(), Prc<T>> {
+        let mut queue = self.lock();
+        if queue.len() >= self.cap {
+            return Err(item);
+        }
+        #[cfg(feature = "paranoid")]
+        self.check_push(&item);
+        queue.push_back(item);
+        Ok(())
+    }
+
+    pub(crate) fn pop(&self) -> Option<Prc<T>> {
+        let item = self.lock().pop_front();
+        #[cfg(feature = "paranoid")]
+        item.iter().for_each(Prc::unmark_queued);
+        item
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.lock().len()
+    }
+}
+
+/// Identity of the pool owning a queue, to check the pushed items with the
+/// `paranoid` feature.
+#[cfg(feature = "paranoid")]
+#[derive(Debug)]
+struct Owner {
+    /// Unique identifier of the pool, never 0.
+    id: usize,
+    /// Name of the pool for panic messages.
+    name: String,
+    /// Next slot index to assign to an item of the pool.
+    next_slot: AtomicUsize,
+}
+
+#[cfg(feature = "paranoid")]
+impl Owner {
+    fn new() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+        Self {
+            id: NEXT_ID.fetch_add(1, Relaxed),
+            name: "<unnamed>".to_owned(),
+            next_slot: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[cfg(feature = "paranoid")]
+impl<T> IdleQueue<T> {
+    /// Set the name of the owning pool used in panic messages.
+    pub(crate) fn named(mut self, name: &str) -> Self {
+        self.owner.name = name.to_owned();
+        self
+    }
+
+    /// Get the name of the owning pool.
+    pub(crate) fn name(&self) -> &str {
+        &self.owner.name
+    }
+
+    /// Check that an item belongs to the pool, claiming it if it belongs to no
+    /// pool yet, and return its slot index.
+    pub(crate) fn check_owner(&self, item: &Prc<T>) -> usize {
+        let (owner, slot) =
+            item.claim_owner(self.owner.id, || self.owner.next_slot.fetch_add(1, Relaxed));
+        assert_eq!(
+            owner, self.owner.id,
+            "slot {slot} of another pool is recycled into pool `{}`",
+            self.owner.name
+        );
+        slot
+    }
+
+    /// Check that an item belongs to the pool and is not in the queue already.
+    fn check_push(&self, item: &Prc<T>) {
+        let slot = self.check_owner(item);
+        assert!(
+            !item.mark_queued(),
+            "slot {slot} is pushed twice into the idle queue of pool `{}`",
+            self.owner.name
+        );
+    }
+}
+
+#[cfg(all(test, feature = "paranoid"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "is pushed twice into the idle queue of pool `test`")]
+    fn double_push_panics() {
+        let queue = IdleQueue::new(2).named("test");
+        let item = Prc::new_zero(0u32, None);
+        queue.push(item.clone()).unwrap();
+        let _ = queue.push(item);
+    }
+}
@@ -0,0 +1,122 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use crate::{Config, Pool, PoolHandle};
+
+/// Process-wide pools, keyed by item type.
+static REGISTRY: LazyLock<Mutex<HashMap<TypeId, Global>>> = LazyLock::new(Mutex::default);
+
+/// Global pool of a type, or its configuration until it is first used.
+enum Global {
+    Config(Box<dyn Any + Send>),
+    /// The pool is being created with this configuration, which is kept for
+    /// the threads racing to create it.
+    Creating(Box<dyn Any + Send>),
+    Pool(Box<dyn Any + Send>),
+}
+
+/// Get the process-wide pool of `T`, creating it on first use with the
+/// configuration registered with [`register_global`], or the default one.
+///
+/// The registry is not locked while the pool is created, so its factory may use
+/// the global pools of other types. If threads race to create the pool, all of
+/// them use the registered configuration and get the first pool registered.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Config, global, register_global};
+///
+/// let mut config = Config::<Vec<u8>>::default();
+/// config.capacity = 8;
+/// assert!(register_global(config));
+/// let item = global::<Vec<u8>>().pull_owned().unwrap();
+/// assert_eq!(global::<Vec<u8>>().capacity(), 8);
+/// assert_eq!(global::<Vec<u8>>().in_use(), 1);
+/// ```
+pub fn global<T>() -> PoolHandle<T>
+where
+    T: Default + Send + Sync + 'static,
+{
+    let id = TypeId::of::<T>();
+    let config = {
+        let mut registry = lock_registry();
+        if let Some(Global::Pool(pool)) = registry.get(&id) {
+            return pool.downcast_ref::<PoolHandle<T>>().unwrap().clone();
+        }
+        let config = match registry.remove(&id) {
+            Some(Global::Config(config) | Global::Creating(config)) => config,
+            _ => Box::new(Config::<T>::default()),
+        };
+        let cloned = config.downcast_ref::<Config<T>>().unwrap().clone();
+        registry.insert(id, Global::Creating(config));
+        cloned
+    };
+    let pool = PoolHandle::from(Pool::with_config(config));
+    let mut registry = lock_registry();
+    // Another thread may have created the pool in the meantime.
+    if let Some(Global::Pool(pool)) = registry.get(&id) {
+        return pool.downcast_ref::<PoolHandle<T>>().unwrap().clone();
+    }
+    registry.insert(id, Global::Pool(Box::new(pool.clone())));
+    pool
+}
+
+/// Register the configuration of the process-wide pool of `T`, returned by
+/// [`global`]. Return `false` if the pool has already been created, or is being
+/// created, in which case the configuration is ignored.
+pub fn register_global<T>(config: Config<T>) -> bool
+where
+    T: Send + Sync + 'static,
+{
+    let mut registry = lock_registry();
+    match registry.get(&TypeId::of::<T>()) {
+        Some(Global::Creating(_) | Global::Pool(_)) => false,
+        _ => {
+            registry.insert(TypeId::of::<T>(), Global::Config(Box::new(config)));
+            true
+        }
+    }
+}
+
+fn lock_registry() -> MutexGuard<'static, HashMap<TypeId, Global>> {
+    REGISTRY.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Declare a `static` pool, created on first use.
+///
+/// Without an initializer, the pool uses the default configuration. Otherwise,
+/// the initializer is a [`Config`] of the pool.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::{Config, static_pool};
+///
+/// static_pool!(static IDS: u64);
+/// static_pool! {
+///     /// Buffers shared by the whole process.
+///     pub static BUFFERS: Vec<u8> = {
+///         let mut config = Config::default();
+///         config.capacity = 16;
+///         config.clear_func = Some(Vec::clear);
+///         config
+///     };
+/// }
+///
+/// let buf = BUFFERS.pull().unwrap();
+/// assert_eq!(BUFFERS.capacity(), 16);
+/// assert_eq!(*IDS.pull().unwrap(), 0);
+/// ```
+#[macro_export]
+macro_rules! static_pool {
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $ty:ty $(;)?) => {
+        $crate::static_pool!($(#[$attr])* $vis static $name: $ty = $crate::Config::default());
+    };
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $ty:ty = $config:expr $(;)?) => {
+        $(#[$attr])*
+        $vis static $name: ::std::sync::LazyLock<$crate::Pool<$ty>> =
+            ::std::sync::LazyLock::new(|| $crate::Pool::with_config($config));
+    };
+}
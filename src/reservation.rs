@@ -0,0 +1,96 @@
+use crate::entry::Prc;
+use crate::{Entry, Pool};
+
+/// Items reserved from the pool which can be pulled later without failing.
+///
+/// Created by [`Pool::reserve`]. Unused reserved items are given back to the
+/// pool when the `Reservation` is dropped.
+#[derive(Debug)]
+pub struct Reservation<'a, T> {
+    pool: &'a Pool<T>,
+    /// Idle items taken out of the pool.
+    items: Vec<Prc<T>>,
+    /// Number of items accounted as allocated but not created yet.
+    unallocated: usize,
+}
+
+impl<'a, T> Reservation<'a, T> {
+    pub(crate) fn new(pool: &'a Pool<T>, items: Vec<Prc<T>>, unallocated: usize) -> Self {
+        Self {
+            pool,
+            items,
+            unallocated,
+        }
+    }
+
+    /// Pull a reserved item. Return `None` if all reserved items have been
+    /// pulled.
+    ///
+    /// Reserved items which are not allocated yet are created on demand, so
+    /// this also returns `None` if a fallible factory fails, in which case the
+    /// reserved slot is given back to the pool.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull(&mut self) -> Option<Entry<'a, T>> {
+        let item = match self.items.pop() {
+            Some(mut item) => {
+                let Ok(fresh) = self.pool.init_slot(&mut item) else {
+                    self.pool.release(vec![item], 0);
+                    return None;
+                };
+                item.checkout();
+                self.pool.before_pull(&mut item, !fresh);
+                item.inc_ref();
+                item
+            }
+            None if self.unallocated > 0 => {
+                self.unallocated -= 1;
+                match self.pool.new_value() {
+                    Ok(value) => {
+                        let mut item = Prc::new(value, self.pool.new_metadata());
+                        self.pool.before_pull(&mut item, false);
+                        item
+                    }
+                    Err(_) => {
+                        self.pool.release(Vec::new(), 1);
+                        return None;
+                    }
+                }
+            }
+            None => return None,
+        };
+        Some(Entry {
+            item: Some(item),
+            pool: self.pool,
+        })
+    }
+
+    /// Pull a reserved item and apply a function to it. Return `None` if all
+    /// reserved items have been pulled.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub fn pull_with<F>(&mut self, func: F) -> Option<Entry<'a, T>>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.pull().map(|mut entry| {
+            self.pool.guarded(&mut entry.item, func);
+            entry
+        })
+    }
+
+    /// Get the number of reserved items which have not been pulled yet.
+    pub fn remaining(&self) -> usize {
+        self.items.len() + self.unallocated
+    }
+
+    /// Get the pool this reservation belongs to.
+    pub fn pool(&self) -> &'a Pool<T> {
+        self.pool
+    }
+}
+
+impl<'a, T> Drop for Reservation<'a, T> {
+    fn drop(&mut self) {
+        self.pool
+            .release(std::mem::take(&mut self.items), self.unallocated);
+    }
+}
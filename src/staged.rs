@@ -0,0 +1,392 @@
+use std::error::Error;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use crate::{
+    BackoffStrategy, Builder, ClearPolicy, ConfigError, ExhaustedPolicy, Pool, PoolHandle,
+    PreallocMode, QuarantineWindow, RecycleDecision,
+};
+
+/// Stages of a [`StagedBuilder`].
+pub mod stage {
+    /// The capacity of the pool is not set yet.
+    #[derive(Debug)]
+    pub struct Initial;
+
+    /// The capacity of the pool is set, so items can be preallocated.
+    #[derive(Debug)]
+    pub struct Capacity;
+
+    /// Auto reclaiming is enabled, so its thresholds can be set.
+    #[derive(Debug)]
+    pub struct AutoReclaim;
+}
+
+/// A builder for creating a [`Pool`] which checks the order of the
+/// configuration at compile time.
+///
+/// Unlike [`Builder`], the capacity must be set before items are preallocated
+/// or the pool is built, and the thresholds of auto reclaiming can only be set
+/// once it is enabled, so they are never silently ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::StagedBuilder;
+///
+/// let pool = StagedBuilder::<Vec<u8>>::new()
+///     .clear_func(Vec::clear)
+///     .capacity(64)
+///     .prealloc(16)
+///     .auto_reclaim()
+///     .idle_threshold_for_surpluspull(8)
+///     .build();
+/// assert_eq!(pool.capacity(), 64);
+/// ```
+///
+/// Setting a threshold without enabling auto reclaiming does not compile:
+///
+/// ```rust,compile_fail
+/// use concurrent_pool::StagedBuilder;
+///
+/// let pool = StagedBuilder::<u32>::new()
+///     .capacity(64)
+///     .idle_threshold_for_surpluspull(8)
+///     .build();
+/// ```
+pub struct StagedBuilder<T, S = stage::Initial> {
+    builder: Builder<T>,
+    capacity: usize,
+    _stage: PhantomData<S>,
+}
+
+impl<T: Default> StagedBuilder<T> {
+    /// Create a new builder with default configuration.
+    pub fn new() -> Self {
+        Self::from_builder(Builder::new())
+    }
+}
+
+impl<T: Default> Default for StagedBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> StagedBuilder<T> {
+    /// Create a new builder with default configuration, creating new items with
+    /// `factory`. See [`Builder::from_factory`].
+    pub fn from_factory<F>(factory: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Self::from_builder(Builder::from_factory(factory))
+    }
+
+    fn from_builder(builder: Builder<T>) -> Self {
+        Self {
+            builder,
+            capacity: 0,
+            _stage: PhantomData,
+        }
+    }
+
+    /// Set the maximum capacity of the pool.
+    pub fn capacity(mut self, capacity: usize) -> StagedBuilder<T, stage::Capacity> {
+        self.builder.capacity(capacity);
+        self.capacity = capacity;
+        self.into_stage()
+    }
+
+    /// Set a soft capacity and let the pool grow beyond it. See
+    /// [`Builder::soft_capacity`].
+    pub fn soft_capacity(
+        mut self,
+        soft_capacity: usize,
+        growth_factor: f64,
+    ) -> StagedBuilder<T, stage::Capacity> {
+        self.builder.soft_capacity(soft_capacity, growth_factor);
+        self.capacity = self.builder.config().capacity;
+        self.into_stage()
+    }
+}
+
+impl<T, S> StagedBuilder<T, S> {
+    fn into_stage<N>(self) -> StagedBuilder<T, N> {
+        StagedBuilder {
+            builder: self.builder,
+            capacity: self.capacity,
+            _stage: PhantomData,
+        }
+    }
+
+    /// Set the function to create new items. See [`Builder::factory`].
+    pub fn factory<F>(mut self, factory: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.builder.factory(factory);
+        self
+    }
+
+    /// Set a fallible function to create new items. See [`Builder::try_factory`].
+    pub fn try_factory<F, E>(mut self, factory: F) -> Self
+    where
+        F: Fn() -> Result<T, E> + Send + Sync + 'static,
+        E: Error + Send + Sync + 'static,
+    {
+        self.builder.try_factory(factory);
+        self
+    }
+
+    /// Set the function to clear an item before it is returned to the pool.
+    pub fn clear_func(mut self, func: fn(&mut T)) -> Self {
+        self.builder.clear_func(func);
+        self
+    }
+
+    /// Set a closure to clear an item before it is returned to the pool. See
+    /// [`Builder::clear_with`].
+    pub fn clear_with<F>(mut self, func: F) -> Self
+    where
+        F: Fn(&mut T) + Send + Sync + 'static,
+    {
+        self.builder.clear_with(func);
+        self
+    }
+
+    /// Set when the clear function is applied, on recycle by default.
+    pub fn clear_policy(mut self, policy: ClearPolicy) -> Self {
+        self.builder.clear_policy(policy);
+        self
+    }
+
+    /// Set the function to apply to an item each time it is pulled.
+    pub fn before_pull(mut self, func: fn(&mut T)) -> Self {
+        self.builder.before_pull(func);
+        self
+    }
+
+    /// Hold recycled items back before they can be reused. See
+    /// [`Builder::quarantine`].
+    pub fn quarantine(mut self, window: QuarantineWindow) -> Self {
+        self.builder.quarantine(window);
+        self
+    }
+
+    /// Set whether a panic of the clear function is resumed. See
+    /// [`Builder::propagate_clear_panics`].
+    pub fn propagate_clear_panics(mut self, propagate: bool) -> Self {
+        self.builder.propagate_clear_panics(propagate);
+        self
+    }
+
+    /// Set the function deciding whether a recycled item is reused. See
+    /// [`Builder::on_recycle`].
+    pub fn on_recycle(mut self, func: fn(&mut T) -> RecycleDecision) -> Self {
+        self.builder.on_recycle(func);
+        self
+    }
+
+    /// Set the function called when a lease is revoked. See
+    /// [`Builder::on_lease_revoked`].
+    pub fn on_lease_revoked(mut self, func: fn(&str, Instant)) -> Self {
+        self.builder.on_lease_revoked(func);
+        self
+    }
+
+    /// Set the function reporting memory pressure. See
+    /// [`Builder::memory_pressure`].
+    pub fn memory_pressure(mut self, func: fn() -> bool) -> Self {
+        self.builder.memory_pressure(func);
+        self
+    }
+
+    /// Report the items held for longer than `threshold`. See
+    /// [`Builder::max_hold_warning`].
+    #[cfg(feature = "instrument")]
+    pub fn max_hold_warning(mut self, threshold: Duration, func: crate::HoldWarningFn) -> Self {
+        self.builder.max_hold_warning(threshold, func);
+        self
+    }
+
+    /// Set the name of the pool. See [`Builder::name`].
+    pub fn name(mut self, name: &str) -> Self {
+        self.builder.name(name);
+        self
+    }
+
+    /// Set what a pull does when the capacity is reached, see [`ExhaustedPolicy`].
+    pub fn on_exhausted(mut self, policy: ExhaustedPolicy) -> Self {
+        self.builder.on_exhausted(policy);
+        self
+    }
+
+    /// Free idle items beyond `prealloc` once they have been idle for longer
+    /// than `timeout`. See [`Builder::idle_timeout`].
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.builder.idle_timeout(timeout);
+        self
+    }
+
+    /// Destroy items older than `lifetime` instead of reusing them. See
+    /// [`Builder::max_lifetime`].
+    pub fn max_lifetime(mut self, lifetime: Duration) -> Self {
+        self.builder.max_lifetime(lifetime);
+        self
+    }
+
+    /// Destroy items once they have been pulled `uses` times. See
+    /// [`Builder::max_uses`].
+    pub fn max_uses(mut self, uses: usize) -> Self {
+        self.builder.max_uses(uses);
+        self
+    }
+
+    /// Set the strategy to back off when the pool appears empty under contention.
+    pub fn backoff(mut self, strategy: BackoffStrategy) -> Self {
+        self.builder.backoff(strategy);
+        self
+    }
+
+    /// Attach metadata of type `M` to each item of the pool. See
+    /// [`Builder::with_metadata`].
+    pub fn with_metadata<M>(mut self) -> Self
+    where
+        M: Default + Send + Sync + 'static,
+    {
+        self.builder.with_metadata::<M>();
+        self
+    }
+}
+
+impl<T> StagedBuilder<T, stage::Capacity> {
+    /// Set the number of preallocated items in the pool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prealloc` is larger than the capacity.
+    pub fn prealloc(mut self, prealloc: usize) -> Self {
+        if prealloc > self.capacity {
+            panic!(
+                "{}",
+                ConfigError::PreallocExceedsCapacity {
+                    prealloc,
+                    capacity: self.capacity,
+                }
+            );
+        }
+        self.builder.prealloc(prealloc);
+        self
+    }
+
+    /// Set how the preallocated items are created, all at once by default.
+    pub fn prealloc_mode(mut self, mode: PreallocMode) -> Self {
+        self.builder.prealloc_mode(mode);
+        self
+    }
+
+    /// Set the minimum number of idle items. See [`Builder::min_idle`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_idle` is larger than the capacity.
+    pub fn min_idle(mut self, min_idle: usize) -> Self {
+        if min_idle > self.capacity {
+            panic!(
+                "{}",
+                ConfigError::MinIdleExceedsCapacity {
+                    min_idle,
+                    capacity: self.capacity,
+                }
+            );
+        }
+        self.builder.min_idle(min_idle);
+        self
+    }
+
+    /// Enable auto reclaiming allocated items and free them to reduce memory usage.
+    pub fn auto_reclaim(mut self) -> StagedBuilder<T, stage::AutoReclaim> {
+        self.builder.enable_auto_reclaim();
+        self.into_stage()
+    }
+
+    /// Build the pool with the current configuration.
+    pub fn build(mut self) -> Pool<T> {
+        self.builder.build()
+    }
+
+    /// Build a shared pool with the current configuration.
+    pub fn build_shared(mut self) -> PoolHandle<T> {
+        self.builder.build_shared()
+    }
+
+    /// Build a shared pool maintained by a background thread every
+    /// `interval`. See [`Builder::spawn_maintenance`].
+    pub fn spawn_maintenance(mut self, interval: Duration) -> PoolHandle<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.builder.spawn_maintenance(interval)
+    }
+}
+
+impl<T> StagedBuilder<T, stage::AutoReclaim> {
+    /// Set the threshold of `surplus-pull` continuous occurrence to trigger
+    /// reclamation.
+    pub fn surpluspull_threshold_for_reclaim(mut self, threshold: usize) -> Self {
+        self.builder.surpluspull_threshold_for_reclaim(threshold);
+        self
+    }
+
+    /// Reclaim idle items with hysteresis. See [`Builder::idle_watermarks`].
+    pub fn idle_watermarks(mut self, low: usize, high: usize) -> Self {
+        self.builder.idle_watermarks(low, high);
+        self
+    }
+
+    /// Set the maximum number of idle items freed each time reclamation is
+    /// triggered. See [`Builder::reclaim_batch`].
+    pub fn reclaim_batch(mut self, batch: usize) -> Self {
+        self.builder.reclaim_batch(batch);
+        self
+    }
+
+    /// Set the threshold for idle items to judge as a `surplus-pull`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is larger than the capacity.
+    pub fn idle_threshold_for_surpluspull(mut self, threshold: usize) -> Self {
+        if threshold > self.capacity {
+            panic!(
+                "{}",
+                ConfigError::IdleThresholdExceedsCapacity {
+                    threshold,
+                    capacity: self.capacity,
+                }
+            );
+        }
+        self.builder.idle_threshold_for_surpluspull(threshold);
+        self
+    }
+
+    /// Build the pool with the current configuration.
+    pub fn build(mut self) -> Pool<T> {
+        self.builder.build()
+    }
+
+    /// Build a shared pool with the current configuration.
+    pub fn build_shared(mut self) -> PoolHandle<T> {
+        self.builder.build_shared()
+    }
+
+    /// Build a shared pool maintained by a background thread every
+    /// `interval`. See [`Builder::spawn_maintenance`].
+    pub fn spawn_maintenance(mut self, interval: Duration) -> PoolHandle<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.builder.spawn_maintenance(interval)
+    }
+}
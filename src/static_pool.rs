@@ -0,0 +1,198 @@
+use std::cell::UnsafeCell;
+use std::fmt::{self, Debug, Display};
+use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering::*;
+
+use crate::PullError;
+
+/// The slot holds no item yet.
+const EMPTY: u8 = 0;
+/// The slot holds an idle item.
+const IDLE: u8 = 1;
+/// The slot is checked out.
+const BUSY: u8 = 2;
+
+/// Slot of a [`StaticPool`].
+struct Slot<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+impl<T> Slot<T> {
+    const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(EMPTY),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+}
+
+/// A pool of at most `N` items stored inline, without any heap allocation.
+///
+/// It can be declared in a `static` as it is created with the `const fn`
+/// [`StaticPool::new`]. Items are created by the `init` function the first time
+/// their slot is pulled, and kept for reuse afterwards, so the steady state
+/// never allocates.
+///
+/// Pulling scans the slots, so `N` is expected to be small.
+///
+/// # Example
+///
+/// ```rust
+/// use concurrent_pool::StaticPool;
+///
+/// static BUFFERS: StaticPool<[u8; 64], 4> = StaticPool::new(|| [0; 64]).clear_func(|b| b.fill(0));
+///
+/// let mut buf = BUFFERS.pull().unwrap();
+/// buf[0] = 1;
+/// assert_eq!(BUFFERS.in_use(), 1);
+/// drop(buf);
+/// assert_eq!(BUFFERS.pull().unwrap()[0], 0);
+/// ```
+pub struct StaticPool<T, const N: usize> {
+    slots: [Slot<T>; N],
+    init: fn() -> T,
+    clear_func: Option<fn(&mut T)>,
+}
+
+unsafe impl<T: Send, const N: usize> Send for StaticPool<T, N> {}
+unsafe impl<T: Send + Sync, const N: usize> Sync for StaticPool<T, N> {}
+
+impl<T, const N: usize> StaticPool<T, N> {
+    /// Create a new pool creating its items with `init` on first use.
+    pub const fn new(init: fn() -> T) -> Self {
+        Self {
+            slots: [const { Slot::new() }; N],
+            init,
+            clear_func: None,
+        }
+    }
+
+    /// Set the function to clear an item before it is returned to the pool.
+    pub const fn clear_func(mut self, func: fn(&mut T)) -> Self {
+        self.clear_func = Some(func);
+        self
+    }
+
+    /// Pull an item from the pool. Return [`PullError::Exhausted`] if all `N`
+    /// items are in use.
+    pub fn pull(&self) -> Result<StaticEntry<'_, T, N>, PullError> {
+        // Reuse idle items before creating new ones.
+        if let Some(index) = self.claim(IDLE) {
+            return Ok(StaticEntry { pool: self, index });
+        }
+        let index = self.claim(EMPTY).ok_or(PullError::Exhausted)?;
+        let guard = EmptyOnUnwind(&self.slots[index]);
+        unsafe { (*self.slots[index].value.get()).write((self.init)()) };
+        std::mem::forget(guard);
+        Ok(StaticEntry { pool: self, index })
+    }
+
+    /// Get the maximum number of items, `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Get the number of items currently in use.
+    pub fn in_use(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| slot.state.load(Relaxed) == BUSY)
+            .count()
+    }
+
+    /// Get the number of items which can be pulled.
+    pub fn available(&self) -> usize {
+        N - self.in_use()
+    }
+
+    /// Claim a slot in `state`, and return its index.
+    fn claim(&self, state: u8) -> Option<usize> {
+        self.slots.iter().position(|slot| {
+            slot.state.load(Relaxed) == state
+                && slot
+                    .state
+                    .compare_exchange(state, BUSY, Acquire, Relaxed)
+                    .is_ok()
+        })
+    }
+
+    /// Return the item of a slot to the pool.
+    fn recycle(&self, index: usize) {
+        let slot = &self.slots[index];
+        if let Some(func) = self.clear_func {
+            let guard = EmptyOnUnwind(slot);
+            func(unsafe { (*slot.value.get()).assume_init_mut() });
+            std::mem::forget(guard);
+        }
+        slot.state.store(IDLE, Release);
+    }
+}
+
+impl<T, const N: usize> Drop for StaticPool<T, N> {
+    fn drop(&mut self) {
+        for slot in &mut self.slots {
+            if *slot.state.get_mut() != EMPTY {
+                unsafe { slot.value.get_mut().assume_init_drop() };
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Debug for StaticPool<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticPool")
+            .field("capacity", &N)
+            .field("in_use", &self.in_use())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Mark a slot as empty if the function creating or clearing its item panics.
+/// The item is leaked in the latter case, as it may be in an unknown state.
+struct EmptyOnUnwind<'a, T>(&'a Slot<T>);
+
+impl<T> Drop for EmptyOnUnwind<'_, T> {
+    fn drop(&mut self) {
+        self.0.state.store(EMPTY, Release);
+    }
+}
+
+/// An item pulled from a [`StaticPool`], returned to the pool when dropped.
+pub struct StaticEntry<'a, T, const N: usize> {
+    pool: &'a StaticPool<T, N>,
+    index: usize,
+}
+
+impl<T, const N: usize> Deref for StaticEntry<'_, T, N> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        unsafe { (*self.pool.slots[self.index].value.get()).assume_init_ref() }
+    }
+}
+
+impl<T, const N: usize> DerefMut for StaticEntry<'_, T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { (*self.pool.slots[self.index].value.get()).assume_init_mut() }
+    }
+}
+
+impl<T, const N: usize> Drop for StaticEntry<'_, T, N> {
+    fn drop(&mut self) {
+        self.pool.recycle(self.index);
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for StaticEntry<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Display, const N: usize> Display for StaticEntry<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
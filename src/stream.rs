@@ -0,0 +1,39 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::{OwnedEntry, Pool, PullError};
+
+/// A stream of owned items pulled from the pool as soon as they are available.
+///
+/// Created by [`Pool::entries_stream`]. When the pool is exhausted, the stream
+/// waits until an item is recycled. Other pull errors, e.g. a failing factory,
+/// are yielded as items. It terminates once the pool is closed.
+#[derive(Debug)]
+pub struct EntriesStream<T> {
+    pool: Arc<Pool<T>>,
+}
+
+impl<T> EntriesStream<T> {
+    pub(crate) fn new(pool: Arc<Pool<T>>) -> Self {
+        Self { pool }
+    }
+
+    /// Get the pool this stream pulls items from.
+    pub fn pool(&self) -> &Arc<Pool<T>> {
+        &self.pool
+    }
+}
+
+impl<T> Stream for EntriesStream<T> {
+    type Item = Result<OwnedEntry<T>, PullError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.pool.poll_pull_owned(cx).map(|result| match result {
+            Err(PullError::Closed) => None,
+            result => Some(result),
+        })
+    }
+}
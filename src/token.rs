@@ -0,0 +1,138 @@
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+use crate::entry::Prc;
+
+/// Number of bits of a token holding the slot index.
+const INDEX_BITS: u32 = 24;
+/// Number of bits of a token holding the slot generation.
+const GENERATION_BITS: u32 = 24;
+/// Number of bits of a token holding the id of the pool.
+const POOL_BITS: u32 = 64 - INDEX_BITS - GENERATION_BITS;
+
+/// An owned entry converted into a plain integer.
+///
+/// Created by [`OwnedEntry::into_raw`](crate::OwnedEntry::into_raw), e.g. to
+/// cross FFI boundaries or to be stored in component arrays. The item stays
+/// checked out until the token is converted back with
+/// [`Pool::entry_from_raw`](crate::Pool::entry_from_raw).
+///
+/// A token refers to a slot of the pool and the generation of that slot, which
+/// is bumped each time a token is converted back. It also carries an id of the
+/// pool, which wraps around every 65536 pools. Stale tokens, e.g. converted back
+/// twice, and tokens of other pools are rejected instead of aliasing another
+/// item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolToken(u64);
+
+impl PoolToken {
+    fn new(pool: u64, index: usize, generation: u32) -> Self {
+        Self(
+            pool << (INDEX_BITS + GENERATION_BITS)
+                | (generation as u64) << INDEX_BITS
+                | index as u64,
+        )
+    }
+
+    fn pool(self) -> u64 {
+        self.0 >> (INDEX_BITS + GENERATION_BITS)
+    }
+
+    fn index(self) -> usize {
+        (self.0 & mask(INDEX_BITS)) as usize
+    }
+
+    fn generation(self) -> u32 {
+        ((self.0 >> INDEX_BITS) & mask(GENERATION_BITS)) as u32
+    }
+
+    /// Get the token as a plain integer.
+    pub fn into_u64(self) -> u64 {
+        self.0
+    }
+
+    /// Create a token from a plain integer returned by [`PoolToken::into_u64`].
+    pub fn from_u64(raw: u64) -> Self {
+        Self(raw)
+    }
+}
+
+/// Get a mask of the `bits` lowest bits.
+const fn mask(bits: u32) -> u64 {
+    (1 << bits) - 1
+}
+
+/// Slots holding the items converted into tokens.
+#[derive(Debug)]
+pub(crate) struct TokenSlots<T> {
+    /// Id of the pool, stored in its tokens.
+    pool: u64,
+    slots: Vec<TokenSlot<T>>,
+    /// Indexes of the vacant slots.
+    vacant: Vec<usize>,
+}
+
+// Only items of `T: Send + Sync` are converted into tokens, see
+// `OwnedEntry::into_raw`.
+unsafe impl<T: Send> Send for TokenSlots<T> {}
+
+#[derive(Debug)]
+struct TokenSlot<T> {
+    generation: u32,
+    item: Option<Prc<T>>,
+}
+
+impl<T> Default for TokenSlots<T> {
+    fn default() -> Self {
+        static NEXT_POOL: AtomicU64 = AtomicU64::new(0);
+        Self {
+            pool: NEXT_POOL.fetch_add(1, Relaxed) & mask(POOL_BITS),
+            slots: Vec::new(),
+            vacant: Vec::new(),
+        }
+    }
+}
+
+impl<T> TokenSlots<T> {
+    /// Store an item in a vacant slot and return the token referring to it.
+    pub(crate) fn insert(&mut self, item: Prc<T>) -> PoolToken {
+        let index = match self.vacant.pop() {
+            Some(index) => index,
+            None => {
+                assert!(
+                    (self.slots.len() as u64) < mask(INDEX_BITS),
+                    "too many pool tokens"
+                );
+                self.slots.push(TokenSlot {
+                    generation: 0,
+                    item: None,
+                });
+                self.slots.len() - 1
+            }
+        };
+        let slot = &mut self.slots[index];
+        slot.item = Some(item);
+        PoolToken::new(self.pool, index, slot.generation)
+    }
+
+    /// Take the item referred to by the token out of its slot. Return `None` if
+    /// the token is stale or was created by another pool.
+    pub(crate) fn take(&mut self, token: PoolToken) -> Option<Prc<T>> {
+        if token.pool() != self.pool {
+            return None;
+        }
+        let slot = self.slots.get_mut(token.index())?;
+        if slot.generation != token.generation() {
+            return None;
+        }
+        let item = slot.item.take()?;
+        slot.generation = slot.generation.wrapping_add(1) & mask(GENERATION_BITS) as u32;
+        self.vacant.push(token.index());
+        Some(item)
+    }
+
+    /// Take all items out of their slots.
+    pub(crate) fn drain(&mut self) -> impl Iterator<Item = Prc<T>> + '_ {
+        self.vacant.clear();
+        self.slots.drain(..).filter_map(|slot| slot.item)
+    }
+}
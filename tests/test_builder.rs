@@ -1,4 +1,6 @@
-use concurrent_pool::Builder;
+use std::sync::Arc;
+
+use concurrent_pool::{BackoffStrategy, Builder};
 
 #[test]
 fn build_pool() {
@@ -62,3 +64,722 @@ fn build_with_auto_reclaim() {
     let _item3 = pool.pull().unwrap();
     assert_eq!(pool.allocated(), 4);
 }
+
+#[test]
+fn build_with_backoff() {
+    let mut builder = Builder::<usize>::new();
+    let pool = Arc::new(
+        builder
+            .capacity(4)
+            .backoff(BackoffStrategy::SpinThenYield)
+            .build(),
+    );
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let pool = pool.clone();
+            std::thread::spawn(move || {
+                for _ in 0..1000 {
+                    let item = pool.pull().unwrap();
+                    drop(item);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(pool.available(), 4);
+    assert!(pool.allocated() <= 4);
+}
+
+#[test]
+fn pull_try_with_recycles_on_error() {
+    let mut builder: Builder<String> = Builder::new();
+    let pool = builder.capacity(1).clear_func(String::clear).build();
+    let result = pool.pull_try_with(|s| {
+        s.push_str("partial");
+        Err::<(), _>("failed")
+    });
+    assert_eq!(result.unwrap().unwrap_err(), "failed");
+    assert_eq!(pool.available(), 1);
+    let item = pool.pull().unwrap();
+    assert_eq!(item.as_str(), "");
+}
+
+#[test]
+fn pull_with_panic_recycles_cleared_item() {
+    let mut builder: Builder<String> = Builder::new();
+    let pool = Arc::new(builder.capacity(1).clear_func(String::clear).build());
+    let clone_pool = pool.clone();
+    let result = std::thread::spawn(move || {
+        clone_pool.pull_owned_with(|s| {
+            s.push_str("partial");
+            panic!("initialization failed");
+        })
+    })
+    .join();
+    assert!(result.is_err());
+    assert_eq!(pool.allocated(), 1);
+    assert_eq!(pool.pull().unwrap().as_str(), "");
+}
+
+#[test]
+fn build_shared_pool() {
+    let pool = Builder::<usize>::new().capacity(4).build_shared();
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let pool = pool.clone();
+            std::thread::spawn(move || pool.pull_owned_with(|x| *x = i).unwrap())
+        })
+        .collect();
+    let mut items: Vec<_> = handles.into_iter().map(|h| *h.join().unwrap()).collect();
+    items.sort();
+    assert_eq!(items, [0, 1, 2, 3]);
+    assert_eq!(pool.available(), 4);
+}
+
+#[test]
+fn pull_clean_and_raw() {
+    let mut builder: Builder<String> = Builder::new();
+    let pool = builder.capacity(1).clear_func(String::clear).build();
+    let item = pool.pull_raw_with(|s| s.push_str("raw")).unwrap();
+    drop(item);
+    // Clear function was skipped on recycle.
+    let item = pool.pull().unwrap();
+    assert_eq!(item.as_str(), "raw");
+    drop(item);
+    // The skip only applies to the raw pull.
+    let item = pool.pull().unwrap();
+    assert_eq!(item.as_str(), "");
+    drop(item);
+
+    let item = pool.pull_raw_with(|s| s.push_str("raw")).unwrap();
+    drop(item);
+    let item = pool.pull_clean().unwrap();
+    assert_eq!(item.as_str(), "");
+}
+
+#[test]
+fn metadata_is_kept_per_slot() {
+    let pool = Builder::<u32>::new()
+        .capacity(2)
+        .prealloc(1)
+        .with_metadata::<Vec<u32>>()
+        .build();
+    let mut item1 = pool.pull().unwrap();
+    let mut item2 = pool.pull().unwrap();
+    item1.metadata_mut::<Vec<u32>>().unwrap().push(1);
+    item2.metadata_mut::<Vec<u32>>().unwrap().push(2);
+    let clone = item1.clone();
+    assert!(item1.metadata_mut::<Vec<u32>>().is_none());
+    drop((item1, clone, item2));
+    let item = pool.pull().unwrap();
+    assert_eq!(item.metadata::<Vec<u32>>(), Some(&vec![1]));
+
+    let plain = Builder::<u32>::new().capacity(1).build();
+    assert!(plain.pull().unwrap().metadata::<Vec<u32>>().is_none());
+}
+
+#[test]
+fn factory_creates_prealloc_and_on_demand_items() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Connection {
+        id: usize,
+    }
+
+    let next_id = Arc::new(AtomicUsize::new(0));
+    let counter = next_id.clone();
+    let pool = Builder::from_factory(move || Connection {
+        id: counter.fetch_add(1, Ordering::Relaxed),
+    })
+    .capacity(3)
+    .prealloc(1)
+    .build();
+    assert_eq!(next_id.load(Ordering::Relaxed), 1);
+    let items: Vec<_> = (0..3).map(|_| pool.pull().unwrap()).collect();
+    let mut ids: Vec<_> = items.iter().map(|c| c.id).collect();
+    ids.sort();
+    assert_eq!(ids, [0, 1, 2]);
+    drop(items);
+    assert_eq!(pool.pull().unwrap().id, 0);
+    assert_eq!(next_id.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn failing_factory_gives_slots_back() {
+    use std::io;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use concurrent_pool::PullError;
+
+    let fail = Arc::new(AtomicBool::new(true));
+    let failing = fail.clone();
+    let pool = Builder::<u32>::new()
+        .capacity(2)
+        .prealloc(1)
+        .try_factory(move || match failing.load(Ordering::Relaxed) {
+            true => Err(io::Error::other("unavailable")),
+            false => Ok(7),
+        })
+        .build();
+    assert_eq!(pool.allocated(), 0);
+    let err = pool.pull().unwrap_err();
+    assert!(matches!(&err, PullError::Factory(e) if e.downcast_ref::<io::Error>().is_some()));
+    assert_eq!(err, err.clone());
+    assert!(matches!(pool.pull_n(2), Err(PullError::Factory(_))));
+    assert_eq!(pool.allocated(), 0);
+
+    fail.store(false, Ordering::Relaxed);
+    let items = pool.pull_n(2).unwrap();
+    assert!(items.iter().all(|item| **item == 7));
+    assert_eq!(pool.allocated(), 2);
+}
+
+#[test]
+fn before_pull_runs_on_every_pull_path() {
+    let pool = Builder::<Vec<u32>>::new()
+        .capacity(3)
+        .prealloc(1)
+        .clear_func(Vec::clear)
+        .before_pull(|v| v.push(1))
+        .build();
+    assert_eq!(*pool.pull().unwrap(), [1]);
+    let items = pool.pull_n(2).unwrap();
+    assert!(items.iter().all(|item| **item == [1]));
+    drop(items);
+    let mut reservation = pool.reserve(3).unwrap();
+    while let Some(item) = reservation.pull() {
+        assert_eq!(*item, [1]);
+    }
+}
+
+#[test]
+fn clear_policies() {
+    use concurrent_pool::ClearPolicy;
+
+    let build = |policy| {
+        Builder::<String>::new()
+            .capacity(1)
+            .clear_func(String::clear)
+            .clear_policy(policy)
+            .build()
+    };
+
+    let pool = build(ClearPolicy::OnPull);
+    drop(pool.pull_raw_with(|s| s.push_str("raw")).unwrap());
+    assert_eq!(&*pool.pull_with(|s| s.push('!')).unwrap(), "raw!");
+    assert_eq!(&*pool.pull().unwrap(), "");
+
+    let pool = build(ClearPolicy::Never);
+    drop(pool.pull_with(|s| s.push_str("kept")).unwrap());
+    assert_eq!(&*pool.pull().unwrap(), "kept");
+}
+
+#[test]
+fn try_build_reports_invalid_configs() {
+    use concurrent_pool::ConfigError;
+
+    let err = Builder::<u32>::new()
+        .capacity(2)
+        .prealloc(3)
+        .try_build()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ConfigError::PreallocExceedsCapacity {
+            prealloc: 3,
+            capacity: 2
+        }
+    );
+    let err = Builder::<u32>::new()
+        .capacity(2)
+        .enable_auto_reclaim()
+        .idle_threshold_for_surpluspull(5)
+        .try_build()
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ConfigError::IdleThresholdExceedsCapacity { threshold: 5, .. }
+    ));
+}
+
+#[test]
+fn staged_builder_builds_in_order() {
+    use concurrent_pool::StagedBuilder;
+
+    let pool = StagedBuilder::from_factory(|| String::with_capacity(8))
+        .clear_func(String::clear)
+        .capacity(4)
+        .prealloc(2)
+        .build();
+    assert_eq!(pool.capacity(), 4);
+    assert_eq!(pool.available_noalloc(), 2);
+    assert!(pool.pull().unwrap().capacity() >= 8);
+}
+
+#[test]
+#[should_panic(expected = "prealloc (5) must be less than or equal to capacity (4)")]
+fn staged_builder_rejects_prealloc_over_capacity() {
+    use concurrent_pool::StagedBuilder;
+
+    let _ = StagedBuilder::<u32>::new().capacity(4).prealloc(5);
+}
+
+#[test]
+fn builder_is_reusable_and_round_trips_config() {
+    use concurrent_pool::Config;
+
+    let mut config = Config::<String>::default();
+    config.capacity = 3;
+    config.clear_func = Some(String::clear);
+    let mut builder = Builder::from_config(config);
+    let mut clone = builder.clone();
+    clone.capacity(5);
+    assert_eq!(builder.config().capacity, 3);
+    assert_eq!(builder.build().capacity(), 3);
+    assert_eq!(builder.build().capacity(), 3);
+    assert_eq!(clone.build().capacity(), 5);
+
+    let pool = Builder::from_config(builder.config().clone()).build();
+    drop(pool.pull_with(|s| s.push_str("dirty")).unwrap());
+    assert_eq!(&*pool.pull().unwrap(), "");
+    assert_eq!(Builder::<u32>::default().build().capacity(), 1024);
+}
+
+#[test]
+fn lazy_prealloc_fills_in_chunks_up_to_prealloc() {
+    use concurrent_pool::PreallocMode;
+
+    let pool = Builder::<u32>::new()
+        .capacity(8)
+        .prealloc(5)
+        .prealloc_mode(PreallocMode::Lazy { chunk: 2 })
+        .build();
+    assert_eq!(pool.allocated(), 0);
+    let items: Vec<_> = (0..6).map(|_| pool.pull().unwrap()).collect();
+    assert_eq!(pool.allocated(), 6);
+    assert_eq!(pool.available_noalloc(), 0);
+    drop(items);
+    assert_eq!(pool.available_noalloc(), 6);
+}
+
+#[test]
+fn deferred_prealloc_constructs_items_on_first_pull() {
+    use concurrent_pool::PreallocMode;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let created = Arc::new(AtomicUsize::new(0));
+    let counter = created.clone();
+    let pool = Builder::from_factory(move || Arc::new(counter.fetch_add(1, Ordering::Relaxed)))
+        .capacity(8)
+        .prealloc(4)
+        .prealloc_mode(PreallocMode::Deferred)
+        .build();
+    assert_eq!(pool.allocated(), 4);
+    assert_eq!(pool.available_noalloc(), 4);
+    assert_eq!(created.load(Ordering::Relaxed), 0);
+
+    let items = pool.pull_n(2).unwrap();
+    let item = pool.pull().unwrap();
+    assert_eq!(created.load(Ordering::Relaxed), 3);
+    assert_eq!(pool.allocated(), 4);
+    drop(items);
+    drop(item);
+    // The untouched slot is dropped with the pool without being constructed.
+    drop(pool);
+}
+
+#[test]
+fn min_idle_replenishes_after_pulls() {
+    use concurrent_pool::ConfigError;
+
+    let pool = Builder::<u32>::new().capacity(4).min_idle(2).build();
+    let items = pool.pull_n(2).unwrap();
+    assert_eq!(pool.available_noalloc(), 2);
+    let item = pool.pull().unwrap();
+    assert_eq!(pool.allocated(), 4);
+    assert_eq!(pool.available_noalloc(), 1);
+    drop(items);
+    drop(item);
+    assert_eq!(pool.available_noalloc(), 4);
+
+    let err = Builder::<u32>::new().capacity(1).min_idle(2).try_build();
+    assert_eq!(
+        err.unwrap_err(),
+        ConfigError::MinIdleExceedsCapacity {
+            min_idle: 2,
+            capacity: 1
+        }
+    );
+}
+
+#[test]
+fn soft_capacity_frees_items_recycled_above_it() {
+    let pool = Builder::<u32>::new().soft_capacity(2, 1.5).build();
+    assert_eq!(pool.capacity(), 3);
+    assert_eq!(pool.config().soft_capacity, 2);
+    let mut items = pool.pull_n(3).unwrap();
+    assert!(pool.pull().is_err());
+    items.pop();
+    assert_eq!(pool.allocated(), 2);
+    assert_eq!(pool.available_noalloc(), 0);
+    drop(items);
+    assert_eq!(pool.allocated(), 2);
+    assert_eq!(pool.available_noalloc(), 2);
+}
+
+#[test]
+fn exhausted_policy_blocks_until_recycled() {
+    use concurrent_pool::ExhaustedPolicy;
+    use std::time::Duration;
+
+    let pool = Builder::<u32>::new()
+        .capacity(1)
+        .on_exhausted(ExhaustedPolicy::Block)
+        .build_shared();
+    let item = pool.pull_owned().unwrap();
+    let clone_pool = pool.clone();
+    let handle = std::thread::spawn(move || *clone_pool.pull_owned().unwrap());
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(!handle.is_finished());
+    drop(item);
+    assert_eq!(handle.join().unwrap(), 0);
+}
+
+#[test]
+fn exhausted_policy_block_spares_fallback_pulls() {
+    use concurrent_pool::ExhaustedPolicy;
+
+    let pool = Builder::<Vec<u8>>::new()
+        .capacity(1)
+        .on_exhausted(ExhaustedPolicy::Block)
+        .build_shared();
+    let mut item = pool.pull_owned_with(|v| v.push(1)).unwrap();
+    assert_eq!(*pool.pull_or_else(|| vec![2]), vec![2]);
+    assert_eq!(*pool.pull_owned_or_else(|| vec![3]), vec![3]);
+    assert!(item.duplicate().is_err());
+
+    // Copy-on-write falls back to an unpooled clone instead of waiting for
+    // the shared item itself to be recycled.
+    let snapshot = item.clone();
+    item.make_mut().push(2);
+    assert_eq!(*item, vec![1, 2]);
+    assert_eq!(*snapshot, vec![1]);
+}
+
+#[test]
+fn exhausted_policy_grows_up_to_max() {
+    use concurrent_pool::{ExhaustedPolicy, PullError};
+
+    let pool = Builder::<u32>::new()
+        .capacity(2)
+        .on_exhausted(ExhaustedPolicy::Grow { max: 3 })
+        .build();
+    let items = pool.pull_n(2).unwrap();
+    let extra = pool.pull().unwrap();
+    assert_eq!(pool.allocated(), 3);
+    assert_eq!(pool.pull().unwrap_err(), PullError::Exhausted);
+    drop(extra);
+    assert_eq!(pool.allocated(), 2);
+    drop(items);
+    assert_eq!(pool.available_noalloc(), 2);
+}
+
+#[test]
+fn reconfigure_reclaim_at_runtime() {
+    let pool = Builder::<usize>::new().capacity(5).prealloc(2).build();
+    let items = pool.pull_n(5).unwrap();
+    drop(items);
+    assert_eq!(pool.allocated(), 5);
+
+    let _item1 = pool.pull().unwrap();
+    assert_eq!(pool.allocated(), 5);
+    pool.reconfigure_reclaim(1, 2, true).unwrap();
+    // First surplus-pull after enabling reclamation.
+    let _item2 = pool.pull().unwrap();
+    assert_eq!(pool.allocated(), 4);
+
+    pool.reconfigure_reclaim(1, 2, false).unwrap();
+    let _item3 = pool.pull().unwrap();
+    assert_eq!(pool.allocated(), 4);
+}
+
+#[test]
+fn reclaim_batch_stops_at_prealloc() {
+    let pool = Builder::<usize>::new()
+        .capacity(6)
+        .prealloc(2)
+        .enable_auto_reclaim()
+        .surpluspull_threshold_for_reclaim(1)
+        .idle_threshold_for_surpluspull(1)
+        .reclaim_batch(10)
+        .build();
+    let items = pool.pull_n(6).unwrap();
+    drop(items);
+    assert_eq!(pool.allocated(), 6);
+
+    // The first surplus-pull frees all the idle items above prealloc.
+    let _item = pool.pull().unwrap();
+    assert_eq!(pool.allocated(), 2);
+    assert_eq!(pool.available_noalloc(), 1);
+}
+
+#[test]
+fn idle_watermarks_reclaim_with_hysteresis() {
+    use concurrent_pool::ConfigError;
+
+    let pool = Builder::<usize>::new()
+        .capacity(8)
+        .prealloc(1)
+        .enable_auto_reclaim()
+        .idle_watermarks(2, 3)
+        .build();
+    let mut items = pool.pull_n(8).unwrap();
+    items.truncate(4);
+    // The fourth recycled item exceeded the high watermark.
+    assert_eq!(pool.available_noalloc(), 2);
+    assert_eq!(pool.allocated(), 6);
+    items.truncate(1);
+    assert_eq!(pool.available_noalloc(), 3);
+    assert_eq!(pool.allocated(), 4);
+    drop(items);
+    assert_eq!(pool.available_noalloc(), 2);
+
+    let err = Builder::<usize>::new().idle_watermarks(3, 2).try_build();
+    assert!(matches!(
+        err,
+        Err(ConfigError::InvalidWatermarks {
+            low: 3,
+            high: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn idle_timeout_frees_expired_items_beyond_prealloc() {
+    use std::time::Duration;
+
+    let pool = Builder::<usize>::new()
+        .capacity(4)
+        .prealloc(1)
+        .idle_timeout(Duration::from_millis(10))
+        .build();
+    drop(pool.pull_n(3).unwrap());
+    assert_eq!(pool.allocated(), 3);
+    let item = pool.pull().unwrap();
+    assert_eq!(pool.allocated(), 3);
+    drop(item);
+
+    std::thread::sleep(Duration::from_millis(20));
+    let _item = pool.pull().unwrap();
+    assert_eq!(pool.allocated(), 1);
+}
+
+#[test]
+fn max_lifetime_replaces_old_items() {
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+    use std::time::Duration;
+
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+    let pool = Builder::<usize>::new()
+        .capacity(2)
+        .prealloc(1)
+        .factory(|| CREATED.fetch_add(1, Relaxed))
+        .max_lifetime(Duration::from_millis(10))
+        .build();
+    assert_eq!(*pool.pull().unwrap(), 0);
+    assert_eq!(*pool.pull().unwrap(), 0);
+
+    std::thread::sleep(Duration::from_millis(20));
+    let item = pool.pull().unwrap();
+    assert_eq!(*item, 1);
+    assert_eq!(pool.allocated(), 1);
+    std::thread::sleep(Duration::from_millis(20));
+    drop(item);
+    assert_eq!(pool.allocated(), 0);
+    assert_eq!(*pool.pull().unwrap(), 2);
+}
+
+#[test]
+fn max_uses_retires_items() {
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+    let pool = Builder::<usize>::new()
+        .capacity(1)
+        .factory(|| CREATED.fetch_add(1, Relaxed))
+        .max_uses(2)
+        .build();
+    assert_eq!(*pool.pull().unwrap(), 0);
+    assert_eq!(*pool.pull().unwrap(), 0);
+    assert_eq!(pool.allocated(), 0);
+    assert_eq!(*pool.pull().unwrap(), 1);
+}
+
+#[test]
+fn maintenance_evicts_idle_items_of_quiet_pool() {
+    use std::time::Duration;
+
+    let pool = Builder::<usize>::new()
+        .capacity(4)
+        .prealloc(1)
+        .idle_timeout(Duration::from_millis(5))
+        .spawn_maintenance(Duration::from_millis(5));
+    drop(pool.pull_n(3).unwrap());
+    assert_eq!(pool.allocated(), 3);
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(pool.allocated(), 1);
+    assert_eq!(pool.available(), 4);
+}
+
+#[test]
+fn maintain_frees_idle_items_under_memory_pressure() {
+    let pool = Builder::<usize>::new()
+        .capacity(4)
+        .prealloc(4)
+        .memory_pressure(|| true)
+        .build();
+    let item = pool.pull().unwrap();
+    pool.maintain();
+    assert_eq!(pool.allocated(), 1);
+    drop(item);
+    assert_eq!(pool.available(), 4);
+}
+
+#[test]
+fn on_recycle_decides_item_fate() {
+    use concurrent_pool::RecycleDecision;
+
+    let pool = Builder::<u32>::new()
+        .capacity(3)
+        .on_recycle(|n| match *n {
+            0 => RecycleDecision::Reuse,
+            1 => RecycleDecision::Discard,
+            _ => RecycleDecision::Quarantine,
+        })
+        .build();
+    let mut items: Vec<_> = (0..3).map(|_| pool.pull().unwrap()).collect();
+    *items[1] = 1;
+    *items[2] = 2;
+    drop(items);
+    assert_eq!(pool.allocated(), 2);
+    assert_eq!(pool.quarantined(), 1);
+    assert_eq!(pool.available(), 2);
+    assert_eq!(pool.take_quarantined(), vec![2]);
+    assert_eq!(pool.available(), 3);
+}
+
+#[test]
+fn clear_func_panic_frees_slot() {
+    let pool = Builder::<u32>::new()
+        .capacity(1)
+        .clear_func(|n| assert_ne!(*n, 1))
+        .propagate_clear_panics(false)
+        .build();
+    *pool.pull().unwrap() = 1;
+    assert_eq!(pool.allocated(), 0);
+    assert_eq!(*pool.pull().unwrap(), 0);
+
+    let pool = Builder::<u32>::new()
+        .capacity(1)
+        .clear_func(|n| assert_ne!(*n, 1))
+        .build();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        *pool.pull().unwrap() = 1;
+    }));
+    assert!(result.is_err());
+    assert_eq!(pool.allocated(), 0);
+    assert!(pool.pull().is_ok());
+}
+
+#[test]
+fn quarantine_delays_reuse() {
+    use concurrent_pool::QuarantineWindow;
+    use std::time::Duration;
+
+    let pool = Builder::<u32>::new()
+        .capacity(2)
+        .quarantine(QuarantineWindow::Duration(Duration::from_millis(10)))
+        .build();
+    *pool.pull().unwrap() = 1;
+    assert_eq!(pool.in_use(), 1);
+    let item = pool.pull().unwrap();
+    assert_eq!(*item, 0);
+    // The pool is exhausted, so the held back item is reused early.
+    assert_eq!(*pool.pull().unwrap(), 1);
+    drop(item);
+
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(*pool.pull().unwrap(), 1);
+    assert_eq!(pool.allocated(), 2);
+}
+
+#[test]
+fn quarantine_wakes_blocked_pulls() {
+    use concurrent_pool::QuarantineWindow;
+    use std::time::Duration;
+
+    let pool = Builder::<u32>::new()
+        .capacity(1)
+        .quarantine(QuarantineWindow::Pulls(100))
+        .build_shared();
+    let item = pool.pull_owned_with(|x| *x = 1).unwrap();
+    let clone_pool = pool.clone();
+    let handle = std::thread::spawn(move || {
+        clone_pool
+            .pull_owned_timeout(Duration::from_secs(10))
+            .map(|item| *item)
+    });
+    std::thread::sleep(Duration::from_millis(20));
+    drop(item);
+    assert_eq!(handle.join().unwrap(), Ok(1));
+}
+
+#[test]
+fn quarantine_duration_serves_blocked_pulls() {
+    use concurrent_pool::{ExhaustedPolicy, QuarantineWindow};
+    use std::time::Duration;
+
+    let pool = Builder::<u32>::new()
+        .capacity(2)
+        .on_exhausted(ExhaustedPolicy::Block)
+        .quarantine(QuarantineWindow::Duration(Duration::from_millis(50)))
+        .build_shared();
+    let _held = pool.pull_owned().unwrap();
+    let item = pool.pull_owned_with(|x| *x = 1).unwrap();
+    let clone_pool = pool.clone();
+    let handle = std::thread::spawn(move || *clone_pool.pull_owned().unwrap());
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(!handle.is_finished());
+    drop(item);
+    std::thread::sleep(Duration::from_millis(60));
+    assert_eq!(handle.join().unwrap(), 1);
+}
+
+#[test]
+#[cfg(feature = "instrument")]
+fn max_hold_warning_reports_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+    use std::time::Duration;
+
+    static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+    let pool = Builder::<u32>::new()
+        .capacity(2)
+        .name("conns")
+        .max_hold_warning(Duration::from_millis(10), |pool, record| {
+            assert_eq!(pool, "conns");
+            assert_eq!(record.location().file(), file!());
+            WARNINGS.fetch_add(1, Relaxed);
+        })
+        .build();
+    let _held = pool.pull().unwrap();
+    drop(pool.pull().unwrap());
+    pool.maintain();
+    assert_eq!(WARNINGS.load(Relaxed), 0);
+    std::thread::sleep(Duration::from_millis(20));
+    pool.maintain();
+    pool.maintain();
+    assert_eq!(WARNINGS.load(Relaxed), 1);
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "bytes")]
+
+use bytes::{Buf, BufMut, BytesMut};
+use concurrent_pool::{Builder, Pool};
+
+#[test]
+fn unique_entry_as_buf_mut() {
+    let pool = Builder::<BytesMut>::new()
+        .capacity(1)
+        .clear_func(BytesMut::clear)
+        .build();
+    let mut buf = pool.pull_unique().unwrap();
+    buf.put_u16(0x0102);
+    buf.put_slice(b"abc");
+    assert_eq!(buf.remaining(), 5);
+    assert_eq!(buf.get_u16(), 0x0102);
+    assert_eq!(buf.chunk(), b"abc");
+    drop(buf);
+    assert!(pool.pull().unwrap().is_empty());
+}
+
+#[test]
+fn entry_as_buf() {
+    let pool = Pool::<std::io::Cursor<Vec<u8>>>::with_capacity(1);
+    let mut item = pool
+        .pull_with(|c| c.get_mut().extend_from_slice(&[1, 2, 3]))
+        .unwrap();
+    assert_eq!(item.get_u8(), 1);
+    let mut rest = [0; 2];
+    item.copy_to_slice(&mut rest);
+    assert_eq!(rest, [2, 3]);
+    assert!(!item.has_remaining());
+}
@@ -0,0 +1,502 @@
+use std::sync::Arc;
+
+use concurrent_pool::{Builder, Pool};
+
+#[test]
+fn unique_entry_mutates_in_place() {
+    let pool = Builder::<Vec<u8>>::new()
+        .capacity(2)
+        .clear_func(Vec::clear)
+        .build();
+    let mut item1 = pool.pull_unique().unwrap();
+    item1.extend_from_slice(b"hello");
+    let mut item2 = pool.pull_unique().unwrap();
+    item2.get_mut().push(1);
+    assert!(pool.pull_unique().is_err());
+    drop(item1);
+    assert_eq!(pool.available(), 1);
+    let item3 = pool.pull().unwrap();
+    assert!(item3.is_empty());
+}
+
+#[test]
+fn unique_entry_across_threads() {
+    let pool = Arc::new(Pool::<String>::with_capacity(4));
+    std::thread::scope(|s| {
+        for i in 0..4 {
+            let pool = &pool;
+            s.spawn(move || {
+                for _ in 0..100 {
+                    let mut item = pool.pull_unique().unwrap();
+                    item.push_str(&i.to_string());
+                }
+            });
+        }
+    });
+    assert_eq!(pool.available(), 4);
+    assert_eq!(pool.allocated(), 4);
+}
+
+#[test]
+fn unique_entry_into_shared() {
+    let pool = Pool::<String>::with_capacity(2);
+    let mut item = pool.pull_unique().unwrap();
+    item.push_str("hello");
+    let mut shared = item.into_shared();
+    assert_eq!(pool.in_use(), 1);
+    let consumers: Vec<_> = (0..3).map(|_| shared.clone()).collect();
+    assert!(shared.get_mut().is_none());
+    std::thread::scope(|s| {
+        for consumer in consumers {
+            s.spawn(move || assert_eq!(consumer.as_str(), "hello"));
+        }
+    });
+    assert!(shared.get_mut().is_some());
+    drop(shared);
+    assert_eq!(pool.in_use(), 0);
+}
+
+#[test]
+fn deref_mut_unique_entry() {
+    let pool = Arc::new(Pool::<String>::with_capacity(2));
+    let mut item = pool.pull().unwrap();
+    item.push_str("hello");
+    let mut owned = pool.pull_owned().unwrap();
+    owned.push_str("world");
+    assert_eq!(item.as_str(), "hello");
+    assert_eq!(owned.as_str(), "world");
+}
+
+#[test]
+#[should_panic(expected = "shared entry")]
+fn deref_mut_shared_entry_panics() {
+    let pool = Pool::<String>::with_capacity(1);
+    let mut item = pool.pull().unwrap();
+    let _clone = item.clone();
+    item.push_str("hello");
+}
+
+#[test]
+fn make_mut_copies_on_write() {
+    let pool = Pool::<Vec<u8>>::with_capacity(2);
+    let mut item = pool.pull_with(|v| v.extend_from_slice(b"abc")).unwrap();
+    // Unique: mutated in place.
+    item.make_mut().push(b'd');
+    assert_eq!(pool.in_use(), 1);
+
+    let snapshot = item.clone();
+    item.make_mut().push(b'e');
+    assert_eq!(&*item, b"abcde");
+    assert_eq!(&*snapshot, b"abcd");
+    assert_eq!(pool.in_use(), 2);
+
+    // Exhausted pool: falls back to an unpooled clone.
+    let mut shared = snapshot.clone();
+    shared.make_mut().push(b'f');
+    assert_eq!(&*shared, b"abcdf");
+    assert_eq!(pool.in_use(), 2);
+    drop(shared);
+    drop(snapshot);
+    drop(item);
+    assert_eq!(pool.in_use(), 0);
+    assert_eq!(pool.allocated(), 2);
+}
+
+#[test]
+fn try_unwrap_takes_value_out() {
+    let pool = Arc::new(Pool::<Vec<u8>>::with_capacity(1));
+    let item = pool
+        .pull_owned_with(|v| v.extend_from_slice(b"abc"))
+        .unwrap();
+    let clone = item.clone();
+    let Err(item) = item.try_unwrap() else {
+        panic!("entry is shared");
+    };
+    assert_eq!(pool.in_use(), 1);
+    drop(clone);
+    let Ok(value) = item.try_unwrap() else {
+        panic!("entry is unique");
+    };
+    assert_eq!(value, b"abc");
+    assert_eq!(pool.in_use(), 0);
+    assert_eq!(pool.allocated(), 1);
+    assert!(pool.pull().unwrap().is_empty());
+}
+
+#[test]
+fn detach_frees_pool_capacity() {
+    let pool = Pool::<Vec<u8>>::with_capacity(2);
+    let item = pool.pull_with(|v| v.push(1)).unwrap();
+    let clone = item.clone();
+    let Err(item) = item.detach() else {
+        panic!("entry is shared");
+    };
+    drop(clone);
+    let cached = item.detach().unwrap();
+    assert_eq!(cached, [1]);
+    assert_eq!(pool.allocated(), 1);
+    assert_eq!(pool.available(), 2);
+
+    let items = pool.pull_n(2).unwrap();
+    assert_eq!(pool.allocated(), 2);
+    drop(items);
+
+    let fallback = pool.pull_or_else(|| vec![2]);
+    let _other = pool.pull().unwrap();
+    let unpooled = pool.pull_or_else(|| vec![3]);
+    assert_eq!(unpooled.detach().unwrap(), [3]);
+    assert_eq!(pool.allocated(), 2);
+    drop(fallback);
+}
+
+#[test]
+fn discard_destroys_item() {
+    let pool = Arc::new(Pool::<Vec<u8>>::with_capacity(2));
+    let item = pool.pull_owned_with(|v| v.push(1)).unwrap();
+    let clone = item.clone();
+    item.discard();
+    assert_eq!(pool.allocated(), 2);
+    assert_eq!(pool.in_use(), 1);
+    drop(clone);
+    assert_eq!(pool.allocated(), 1);
+    assert_eq!(pool.in_use(), 0);
+
+    let unique = pool.pull_unique().unwrap();
+    unique.discard();
+    assert_eq!(pool.allocated(), 0);
+
+    let items = pool.pull_n(2).unwrap();
+    assert!(items.iter().all(|v| v.is_empty()));
+}
+
+#[test]
+fn weak_entry_does_not_keep_item() {
+    let pool = Pool::<String>::with_capacity(1);
+    let mut item = pool.pull_with(|s| s.push_str("hello")).unwrap();
+    let weak = item.downgrade();
+    assert!(item.get_mut().is_none());
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(&*upgraded, "hello");
+    drop(upgraded);
+    drop(item);
+    assert_eq!(pool.available(), 1);
+    assert!(weak.upgrade().is_none());
+
+    // A new checkout of the same slot is not reachable from the old weak entry.
+    let mut item = pool.pull().unwrap();
+    assert!(weak.upgrade().is_none());
+    assert!(item.get_mut().is_some());
+}
+
+#[test]
+fn dropped_weak_entries_restore_uniqueness() {
+    let pool = Pool::<String>::with_capacity(1);
+    let mut item = pool.pull().unwrap();
+    let weak = item.downgrade();
+    let weak_clone = weak.clone();
+    drop(weak);
+    assert!(item.get_mut().is_none());
+    drop(weak_clone);
+    assert!(item.get_mut().is_some());
+    item.push_str("hello");
+
+    // Weak entries of a previous checkout do not count for the next one.
+    let stale = item.downgrade();
+    drop(item);
+    let mut item = pool.pull().unwrap();
+    let weak = item.downgrade();
+    drop(stale);
+    assert!(item.get_mut().is_none());
+    drop(weak);
+    assert!(item.get_mut().is_some());
+}
+
+#[test]
+fn weak_entry_outlives_destroyed_item() {
+    let pool = Arc::new(Pool::<Vec<u8>>::with_capacity(1));
+    let item = pool.pull_owned().unwrap();
+    let weak = item.downgrade();
+    let weak_clone = weak.clone();
+    item.discard();
+    assert_eq!(pool.allocated(), 0);
+    assert!(weak.upgrade().is_none());
+    drop(weak);
+    assert!(weak_clone.upgrade().is_none());
+}
+
+#[test]
+fn weak_entry_across_threads() {
+    let pool = Arc::new(Pool::<usize>::with_capacity(2));
+    std::thread::scope(|s| {
+        for i in 0..4 {
+            let pool = &pool;
+            s.spawn(move || {
+                for _ in 0..1000 {
+                    if let Ok(item) = pool.pull_owned_with(|x| *x = i) {
+                        let weak = item.downgrade();
+                        if let Some(upgraded) = weak.upgrade() {
+                            assert_eq!(*upgraded, i);
+                        }
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!(pool.available(), pool.allocated());
+}
+
+#[test]
+fn mapped_entry_keeps_item_checked_out() {
+    let pool = Pool::<(u32, Vec<u8>)>::with_capacity(1);
+    let item = pool.pull_with(|t| t.1.push(1)).unwrap();
+    let mapped = item.map(|t| &t.1);
+    let mapped_clone = mapped.clone();
+    std::thread::scope(|s| {
+        s.spawn(move || assert_eq!(*mapped_clone, vec![1]));
+    });
+    assert_eq!(pool.in_use(), 1);
+    let item = mapped.into_entry();
+    assert_eq!(item.1, vec![1]);
+    drop(item);
+    assert_eq!(pool.available(), 1);
+}
+
+#[test]
+fn owned_mapped_entry_across_threads() {
+    let pool = Arc::new(Pool::<(u32, String)>::with_capacity(2));
+    let (tx, rx) = std::sync::mpsc::channel();
+    for i in 0..2 {
+        let item = pool.pull_owned_with(|t| t.0 = i).unwrap();
+        tx.send(item.map_owned(|t| &t.0)).unwrap();
+    }
+    drop(tx);
+    let handle = std::thread::spawn(move || rx.iter().map(|id| *id).sum::<u32>());
+    assert_eq!(handle.join().unwrap(), 1);
+    assert_eq!(pool.available(), 2);
+}
+
+#[test]
+fn into_owned_keeps_reference_count() {
+    let pool = Arc::new(Pool::<u32>::with_capacity(1));
+    let item = pool.pull_with(|x| *x = 1).unwrap();
+    let clone = item.clone();
+    let owned = item.into_owned(&pool);
+    drop(clone);
+    assert_eq!(pool.in_use(), 1);
+    drop(owned);
+    assert_eq!(pool.available(), 1);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the entry does not belong to the given pool")]
+fn into_owned_checks_pool() {
+    let pool = Pool::<u32>::with_capacity(1);
+    let other = Arc::new(Pool::<u32>::with_capacity(1));
+    let _ = pool.pull().unwrap().into_owned(&other);
+}
+
+#[test]
+fn leak_shared_entry() {
+    let pool = Arc::new(Pool::<String>::with_capacity(1));
+    let item = pool.pull_owned_with(|s| s.push_str("global")).unwrap();
+    let clone = item.clone();
+    let leaked = item.leak();
+    drop(clone);
+    assert_eq!(pool.allocated(), 0);
+    let other = pool.pull().unwrap();
+    assert!(other.is_empty());
+    drop(other);
+    drop(pool);
+    assert_eq!(leaked, "global");
+}
+
+#[test]
+// Entries hash and compare by the inner item only, not the reference count.
+#[allow(clippy::mutable_key_type)]
+fn entries_in_sets_and_sorted() {
+    let pool = Arc::new(Pool::<u32>::with_capacity(6));
+    let mut entries = vec![];
+    for i in [3, 1, 2, 1] {
+        entries.push(pool.pull_with(|x| *x = i).unwrap());
+    }
+    entries.sort();
+    assert_eq!(
+        entries.iter().map(|e| **e).collect::<Vec<_>>(),
+        [1, 1, 2, 3]
+    );
+    let set: std::collections::HashSet<_> = entries.iter().cloned().collect();
+    assert_eq!(set.len(), 3);
+
+    let owned: std::collections::BTreeSet<_> = (0..2)
+        .map(|i| pool.pull_owned_with(|x| *x = i).unwrap().map_owned(|x| x))
+        .collect();
+    assert_eq!(owned.first().map(|e| **e), Some(0));
+}
+
+#[test]
+fn entries_format_as_inner_item() {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Response<'a> {
+        body: concurrent_pool::Entry<'a, String>,
+    }
+
+    let pool = Arc::new(Pool::<String>::with_capacity(2));
+    let body = pool.pull_with(|s| s.push_str("ok")).unwrap();
+    assert_eq!(
+        format!("{:?}", Response { body }),
+        r#"Response { body: "ok" }"#
+    );
+    let owned = pool.pull_owned_with(|s| s.push_str("owned")).unwrap();
+    assert_eq!(owned.to_string(), "owned");
+    assert_eq!(format!("{owned:?}"), r#""owned""#);
+}
+
+#[test]
+#[allow(clippy::mutable_key_type)]
+fn entries_as_ref_and_borrow() {
+    fn checksum(data: impl AsRef<[u8]>) -> u32 {
+        data.as_ref().iter().map(|&b| b as u32).sum()
+    }
+
+    let bytes = Pool::<Vec<u8>>::with_capacity(1);
+    let item = bytes
+        .pull_with(|v| v.extend_from_slice(&[1, 2, 3]))
+        .unwrap();
+    assert_eq!(checksum(&item), 6);
+    let strings = Arc::new(Pool::<String>::with_capacity(1));
+    let item = strings.pull_owned_with(|s| s.push('a')).unwrap();
+    assert_eq!(checksum(&item), 97);
+    let map: std::collections::HashMap<_, _> = [(item, 1)].into_iter().collect();
+    assert_eq!(map.get(&String::from("a")), Some(&1));
+}
+
+#[test]
+fn stale_tokens_are_rejected() {
+    let pool = Arc::new(Pool::<u32>::with_capacity(2));
+    let other = Arc::new(Pool::<u32>::with_capacity(1));
+    let token = pool.pull_owned_with(|x| *x = 1).unwrap().into_raw();
+    let other_token = other.pull_owned().unwrap().into_raw();
+    assert!(other.entry_from_raw(token).is_none());
+    assert!(pool.entry_from_raw(other_token).is_none());
+    assert!(other.entry_from_raw(other_token).is_some());
+    let item = pool.entry_from_raw(token).unwrap();
+    assert!(pool.entry_from_raw(token).is_none());
+
+    // The slot of the token is reused with a new generation.
+    let token2 = pool.pull_owned_with(|x| *x = 2).unwrap().into_raw();
+    assert_ne!(token, token2);
+    assert!(pool.entry_from_raw(token).is_none());
+    assert_eq!(*pool.entry_from_raw(token2).unwrap(), 2);
+    drop(item);
+    assert_eq!(pool.available(), 2);
+
+    // Items held by tokens are released when the pool is dropped.
+    let _token = pool.pull_owned().unwrap().into_raw();
+    drop(pool);
+}
+
+#[test]
+#[cfg(feature = "instrument")]
+fn held_for_restarts_on_each_pull() {
+    use std::time::Duration;
+
+    let pool = Pool::<u32>::with_capacity(1);
+    let item = pool.pull().unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(item.held_for() >= Duration::from_millis(20));
+    drop(item);
+    let item = pool.pull_unique().unwrap();
+    assert!(item.held_for() < Duration::from_millis(20));
+}
+
+#[test]
+fn use_count_per_item() {
+    let pool = Pool::<u32>::with_capacity(2);
+    let items = pool.pull_n(2).unwrap();
+    assert!(items.iter().all(|item| item.use_count() == 1));
+    drop(items);
+    let mut reservation = pool.reserve(1).unwrap();
+    assert_eq!(reservation.pull().unwrap().use_count(), 2);
+    drop(reservation);
+    let unique = pool.pull_unique().unwrap();
+    let shared = pool.pull().unwrap();
+    assert_eq!(unique.use_count() + shared.use_count(), 2 + 3);
+    assert_eq!(pool.pull_or_else(|| 0).use_count(), 1);
+}
+
+#[test]
+fn entries_as_io_read_write() {
+    use std::io::{Cursor, Read, Write};
+
+    let pool = Builder::<Vec<u8>>::new()
+        .capacity(2)
+        .clear_func(Vec::clear)
+        .build();
+    let mut buf = pool.pull_unique().unwrap();
+    write!(buf, "{}-{}", 1, 2).unwrap();
+    assert_eq!(&*buf, b"1-2");
+
+    let mut item = pool.pull().unwrap();
+    item.write_all(b"abc").unwrap();
+    let clone = item.clone();
+    assert!(item.write_all(b"d").is_err());
+    drop(clone);
+
+    let cursors = Pool::<Cursor<Vec<u8>>>::with_capacity(1);
+    let mut cursor = cursors.pull_with(|c| c.get_mut().push(7)).unwrap();
+    let mut out = vec![];
+    cursor.read_to_end(&mut out).unwrap();
+    assert_eq!(out, [7]);
+}
+
+#[test]
+fn unique_entries_of_send_only_items() {
+    use std::cell::Cell;
+
+    let pool = Pool::<Cell<u32>>::with_capacity(2);
+    std::thread::scope(|s| {
+        for _ in 0..2 {
+            let item = pool.pull_unique().unwrap();
+            s.spawn(move || {
+                let cell: &Cell<u32> = &item;
+                cell.set(cell.get() + 1);
+            });
+        }
+    });
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            for _ in 0..10 {
+                let item = pool.pull_unique().unwrap();
+                assert!(Cell::get(&item) <= 1);
+            }
+        });
+    });
+    assert_eq!(pool.available(), 2);
+}
+
+#[test]
+#[should_panic(expected = "shared entry")]
+fn take_from_shared_entry_panics() {
+    let pool = Pool::<Vec<u8>>::with_capacity(1);
+    let mut item = pool.pull().unwrap();
+    let _clone = item.clone();
+    item.take();
+}
+
+#[test]
+fn split_mut_keeps_item_until_both_halves_drop() {
+    let pool = Pool::<(String, Vec<u8>)>::with_capacity(1);
+    let item = pool.pull_unique().unwrap();
+    let (mut name, mut data) = item.split_mut(|t| (&mut t.0, &mut t.1));
+    name.push_str("ab");
+    data.push(1);
+    drop(name);
+    assert_eq!(pool.in_use(), 1);
+    assert_eq!(*data, [1]);
+    drop(data);
+    assert_eq!(pool.in_use(), 0);
+    let item = pool.pull().unwrap();
+    assert_eq!(item.0, "ab");
+}
@@ -1,6 +1,7 @@
 use std::sync::{Arc, mpsc};
+use std::time::{Duration, Instant};
 
-use concurrent_pool::Pool;
+use concurrent_pool::{Pool, PullError, PullKind};
 
 #[derive(Debug)]
 struct BigStruct {
@@ -185,3 +186,671 @@ fn two_send_thread_two_recv_thread() {
     receiver1_handle.join().unwrap();
     receiver2_handle.join().unwrap();
 }
+
+#[test]
+fn notified_when_item_recycled() {
+    let pool = Arc::new(Pool::<BigStruct>::with_capacity(1));
+    let item = pool.pull_owned().unwrap();
+    assert_eq!(pool.available(), 0);
+
+    let waiter_pool = pool.clone();
+    let waiter = std::thread::spawn(move || {
+        futures::executor::block_on(waiter_pool.notified());
+        waiter_pool.pull().is_ok()
+    });
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    drop(item);
+    assert!(waiter.join().unwrap());
+}
+
+#[test]
+fn pull_or_else_fallback_is_not_recycled() {
+    let pool = Arc::new(Pool::<BigStruct>::with_capacity(1));
+    let item1 = pool.pull_owned_or_else(BigStruct::default);
+    let item2 = pool.pull_owned_or_else(|| BigStruct {
+        str: "fallback".to_string(),
+        ..Default::default()
+    });
+    assert_eq!(item2.str.as_str(), "fallback");
+    let item2_clone = item2.clone();
+    drop(item2);
+    drop(item2_clone);
+    assert_eq!(pool.available(), 0);
+    drop(item1);
+    assert_eq!(pool.available(), 1);
+    assert_eq!(pool.allocated(), 1);
+    assert_eq!(pool.pull().unwrap().str.as_str(), "Hello");
+}
+
+#[test]
+fn pull_n_is_all_or_nothing() {
+    let pool = Pool::<BigStruct>::new(3, 10);
+    let items = pool.pull_n(6).unwrap();
+    assert_eq!(items.len(), 6);
+    assert_eq!(pool.allocated(), 6);
+    assert!(pool.pull_n(5).is_err());
+    assert_eq!(pool.available(), 4);
+    assert_eq!(pool.allocated(), 6);
+    drop(items);
+    assert_eq!(pool.available(), 10);
+    assert_eq!(pool.available_noalloc(), 6);
+}
+
+#[test]
+fn pull_up_to_is_best_effort() {
+    let pool = Arc::new(Pool::<BigStruct>::new(3, 10));
+    let items = pool.pull_owned_up_to(4);
+    assert_eq!(items.len(), 4);
+    let more = pool.pull_owned_up_to(100);
+    assert_eq!(more.len(), 6);
+    assert!(pool.pull_owned_up_to(1).is_empty());
+    drop(items);
+    assert_eq!(pool.available(), 4);
+}
+
+#[test]
+fn reservation_guarantees_pulls() {
+    let pool = Arc::new(Pool::<BigStruct>::new(2, 4));
+    let mut reservation = pool.reserve(3).unwrap();
+    assert_eq!(pool.available(), 1);
+
+    let other_pool = pool.clone();
+    std::thread::spawn(move || {
+        let item = other_pool.pull().unwrap();
+        assert!(other_pool.pull().is_err());
+        drop(item);
+    })
+    .join()
+    .unwrap();
+
+    let items: Vec<_> = std::iter::from_fn(|| reservation.pull()).collect();
+    assert_eq!(items.len(), 3);
+    assert_eq!(reservation.remaining(), 0);
+    drop(reservation);
+    assert_eq!(pool.available(), 1);
+    drop(items);
+    assert_eq!(pool.available(), 4);
+}
+
+#[test]
+fn reservation_gives_back_unused_items() {
+    let pool = Pool::<BigStruct>::new(1, 3);
+    let reservation = pool.reserve(3).unwrap();
+    assert_eq!(pool.allocated(), 3);
+    assert_eq!(pool.available(), 0);
+    drop(reservation);
+    assert_eq!(pool.allocated(), 1);
+    assert_eq!(pool.available(), 3);
+    assert_eq!(pool.available_noalloc(), 1);
+}
+
+#[test]
+fn pull_errors() {
+    let pool = Pool::<BigStruct>::with_capacity(1);
+    let item = pool.pull().unwrap();
+    assert_eq!(pool.pull().unwrap_err(), PullError::Exhausted);
+    assert_eq!(pool.pull_n(1).unwrap_err(), PullError::Exhausted);
+    assert_eq!(
+        pool.pull_timeout(Duration::from_millis(5)).unwrap_err(),
+        PullError::Timeout
+    );
+    drop(item);
+    assert!(pool.pull_timeout(Duration::from_millis(5)).is_ok());
+}
+
+#[test]
+fn pull_timeout_waits_for_recycle() {
+    let pool = Arc::new(Pool::<BigStruct>::with_capacity(2));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let pool = pool.clone();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    let item = pool.pull_owned_timeout(Duration::from_secs(10)).unwrap();
+                    std::thread::yield_now();
+                    drop(item);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(pool.available(), 2);
+}
+
+#[test]
+fn pull_tracked_reports_kind() {
+    let pool = Pool::<BigStruct>::new(2, 4);
+    let kinds: Vec<_> = (0..4).map(|_| pool.pull_tracked().unwrap()).collect();
+    assert_eq!(
+        kinds.iter().map(|(_, kind)| *kind).collect::<Vec<_>>(),
+        vec![
+            PullKind::Reused,
+            PullKind::Reused,
+            PullKind::FreshlyAllocated,
+            PullKind::FreshlyAllocated
+        ]
+    );
+    drop(kinds);
+    let (_item, kind) = pool.pull_tracked().unwrap();
+    assert_eq!(kind, PullKind::Reused);
+}
+
+#[test]
+fn pull_with_panic_discards_item() {
+    let pool = Arc::new(Pool::<BigStruct>::with_capacity(2));
+    let clone_pool = pool.clone();
+    let result = std::thread::spawn(move || {
+        let _ = clone_pool.pull_with(|item| {
+            item.str.push_str(" World");
+            panic!("initialization failed");
+        });
+    })
+    .join();
+    assert!(result.is_err());
+    assert_eq!(pool.allocated(), 1);
+    assert_eq!(pool.available(), 2);
+    let item1 = pool.pull().unwrap();
+    let item2 = pool.pull().unwrap();
+    assert_eq!(item1.str.as_str(), "Hello");
+    assert_eq!(item2.str.as_str(), "Hello");
+}
+
+#[test]
+fn pull_until_shares_deadline() {
+    let pool = Pool::<BigStruct>::with_capacity(2);
+    let deadline = Instant::now() + Duration::from_millis(20);
+    let item1 = pool.pull_until(deadline).unwrap();
+    let item2 = pool.pull_until(deadline).unwrap();
+    assert_eq!(pool.pull_until(deadline).unwrap_err(), PullError::Timeout);
+    assert!(Instant::now() >= deadline);
+    // An elapsed deadline still allows pulling available items.
+    drop(item1);
+    assert!(pool.pull_until(deadline).is_ok());
+    drop(item2);
+}
+
+#[test]
+fn interrupt_blocked_pulls() {
+    let pool = Arc::new(Pool::<BigStruct>::with_capacity(1));
+    let interrupter = pool.interrupter();
+    let item = pool.pull_owned().unwrap();
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let pool = pool.clone();
+            let interrupter = interrupter.clone();
+            std::thread::spawn(move || pool.pull_interruptible(&interrupter).map(|_| ()))
+        })
+        .collect();
+    std::thread::sleep(Duration::from_millis(10));
+    interrupter.interrupt();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Err(PullError::Interrupted));
+    }
+
+    interrupter.reset();
+    drop(item);
+    assert!(pool.pull_interruptible(&interrupter).is_ok());
+}
+
+#[test]
+fn scoped_threads_share_entries() {
+    let pool = Pool::<BigStruct>::with_capacity(4);
+    let (tx, rx) = mpsc::channel();
+    std::thread::scope(|s| {
+        for i in 0..4 {
+            let tx = tx.clone();
+            let pool = &pool;
+            s.spawn(move || {
+                let item = pool.pull_with(|item| item.str = i.to_string()).unwrap();
+                tx.send(item).unwrap();
+            });
+        }
+        drop(tx);
+        s.spawn(move || {
+            let mut items: Vec<_> = rx.iter().collect();
+            items.sort_by(|a, b| a.str.cmp(&b.str));
+            let strs: Vec<_> = items.iter().map(|item| item.str.as_str()).collect();
+            assert_eq!(strs, ["0", "1", "2", "3"]);
+        });
+    });
+    assert_eq!(pool.available(), 4);
+}
+
+#[test]
+fn pull_from_moves_value_into_slot() {
+    let pool = Pool::<BigStruct>::new(1, 2);
+    let value = BigStruct {
+        str: "reused".to_string(),
+        ..Default::default()
+    };
+    let item1 = pool.pull_from(value).unwrap();
+    assert_eq!(item1.str.as_str(), "reused");
+    assert_eq!(pool.allocated(), 1);
+    let value = BigStruct {
+        str: "fresh".to_string(),
+        ..Default::default()
+    };
+    let item2 = pool.pull_from(value).unwrap();
+    assert_eq!(item2.str.as_str(), "fresh");
+    assert_eq!(pool.allocated(), 2);
+
+    let (value, err) = pool.pull_from(BigStruct::default()).unwrap_err();
+    assert_eq!(value.str.as_str(), "Hello");
+    assert_eq!(err, PullError::Exhausted);
+
+    drop(item1);
+    drop(item2);
+    let strs: Vec<_> = pool
+        .pull_n(2)
+        .unwrap()
+        .iter()
+        .map(|item| item.str.clone())
+        .collect();
+    assert_eq!(strs, ["reused", "fresh"]);
+}
+
+#[test]
+fn pinned_items_are_dropped_before_reuse() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Default)]
+    struct Tracked {
+        value: u32,
+        _pin: std::marker::PhantomPinned,
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            if self.value != 0 {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    let pool = Arc::new(Pool::<Tracked>::with_capacity(1));
+    let mut item = pool.pull_owned_pinned().unwrap();
+    let addr = &*item as *const Tracked;
+    unsafe { item.as_mut().get_unchecked_mut().value = 7 };
+    let clone = item.clone();
+    drop(item);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+    drop(clone);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+    let item = pool.pull().unwrap();
+    assert_eq!(item.value, 0);
+    assert_eq!(&*item as *const Tracked, addr);
+}
+
+#[test]
+fn warm_up_allocates_idle_items_within_capacity() {
+    let pool: Pool<u32> = Pool::new(1, 4);
+    let item = pool.pull().unwrap();
+    assert_eq!(pool.warm_up(2), 2);
+    assert_eq!(pool.allocated(), 3);
+    assert_eq!(pool.available_noalloc(), 2);
+    assert_eq!(pool.warm_up(5), 1);
+    assert_eq!(pool.warm_up(1), 0);
+    drop(item);
+    assert_eq!(pool.available_noalloc(), 4);
+}
+
+#[test]
+fn close_wakes_blocked_pulls() {
+    let pool = Arc::new(Pool::<BigStruct>::with_capacity(1));
+    let item = pool.pull_owned().unwrap();
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let pool = pool.clone();
+            std::thread::spawn(move || pool.pull_owned_timeout(Duration::from_secs(10)).map(|_| ()))
+        })
+        .collect();
+    std::thread::sleep(Duration::from_millis(10));
+    pool.close();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Err(PullError::Closed));
+    }
+    assert_eq!(pool.pull_n(1).unwrap_err(), PullError::Closed);
+    // Outstanding entries are still usable.
+    assert_eq!(item.str, "Hello");
+}
+
+#[test]
+fn items_are_freed_after_close() {
+    let pool = Pool::<BigStruct>::new(2, 4);
+    let item1 = pool.pull().unwrap();
+    let item2 = pool.pull().unwrap();
+    let item3 = pool.pull().unwrap();
+    assert_eq!(pool.allocated(), 3);
+    drop(item1);
+    pool.close();
+    assert_eq!(pool.allocated(), 2);
+    assert_eq!(pool.available_noalloc(), 0);
+    drop(item2);
+    assert_eq!(pool.allocated(), 1);
+    drop(item3);
+    assert_eq!(pool.allocated(), 0);
+    assert_eq!(pool.available_noalloc(), 0);
+}
+
+#[test]
+fn close_frees_items_recycled_concurrently() {
+    for _ in 0..50 {
+        let pool = Pool::<usize>::with_capacity(8);
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| while pool.pull().is_ok() {});
+            }
+            s.spawn(|| pool.close());
+        });
+        assert_eq!(pool.allocated(), 0);
+    }
+}
+
+#[test]
+fn global_pool_is_shared_per_type() {
+    #[derive(Default)]
+    struct Connection(u32);
+
+    assert!(concurrent_pool::register_global(concurrent_pool::Config::<
+        Connection,
+    >::default()));
+    let item = concurrent_pool::global::<Connection>()
+        .pull_owned_with(|c| c.0 = 7)
+        .unwrap();
+    assert_eq!(item.0, 7);
+    assert_eq!(concurrent_pool::global::<Connection>().in_use(), 1);
+    assert!(!concurrent_pool::register_global(
+        concurrent_pool::Config::<Connection>::default()
+    ));
+}
+
+#[test]
+fn global_pool_factory_may_use_other_global_pools() {
+    #[derive(Default)]
+    struct Session(usize);
+
+    let mut config = concurrent_pool::Config::with_factory(|| {
+        Session(concurrent_pool::global::<Vec<u16>>().capacity())
+    });
+    config.prealloc = 1;
+    assert!(concurrent_pool::register_global(config));
+    let session = concurrent_pool::global::<Session>().pull_owned().unwrap();
+    assert_eq!(session.0, concurrent_pool::global::<Vec<u16>>().capacity());
+}
+
+#[test]
+fn global_pool_keeps_registered_config_while_created() {
+    #[derive(Default)]
+    struct Token(bool);
+
+    let mut config = concurrent_pool::Config::with_factory(|| {
+        // The pool is being created, so its configuration is already taken.
+        Token(!concurrent_pool::register_global(
+            concurrent_pool::Config::<Token>::default(),
+        ))
+    });
+    config.capacity = 3;
+    config.prealloc = 1;
+    assert!(concurrent_pool::register_global(config));
+    let pool = concurrent_pool::global::<Token>();
+    assert_eq!(pool.capacity(), 3);
+    assert!(pool.pull().unwrap().0);
+}
+
+#[test]
+fn static_pool_creates_items_lazily() {
+    use concurrent_pool::StaticPool;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+    static POOL: StaticPool<String, 2> = StaticPool::new(|| {
+        CREATED.fetch_add(1, Ordering::Relaxed);
+        String::new()
+    })
+    .clear_func(String::clear);
+
+    let mut item1 = POOL.pull().unwrap();
+    item1.push_str("hello");
+    assert_eq!(CREATED.load(Ordering::Relaxed), 1);
+    let item2 = POOL.pull().unwrap();
+    assert_eq!(POOL.pull().unwrap_err(), PullError::Exhausted);
+    drop(item1);
+    drop(item2);
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..100 {
+                    if let Ok(item) = POOL.pull() {
+                        assert!(item.is_empty());
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!(CREATED.load(Ordering::Relaxed), 2);
+    assert_eq!(POOL.available(), 2);
+}
+
+#[test]
+fn pool_map_keeps_pools_in_use() {
+    use concurrent_pool::{Config, PoolMap};
+
+    let map = PoolMap::<usize, Vec<u8>>::new(Config::default());
+    let small = map.pool(&64);
+    let item = map.pull_owned(&4096).unwrap();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.evict_idle(), 0);
+    assert!(small.pull().is_ok());
+    drop(small);
+    drop(item);
+    assert_eq!(map.allocated(), 2);
+    assert_eq!(map.evict_idle(), 2);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn cached_pool_flushes_on_thread_exit() {
+    use concurrent_pool::CachedPool;
+
+    let pool = Arc::new(Pool::<BigStruct>::new(0, 4));
+    let cached = Arc::new(CachedPool::new(pool.clone(), 1));
+    let clone_cached = cached.clone();
+    std::thread::spawn(move || {
+        let item1 = clone_cached.pull().unwrap();
+        let item2 = clone_cached.pull().unwrap();
+        drop(item1);
+        // The cache of this thread is full.
+        drop(item2);
+        assert_eq!(clone_cached.pool().in_use(), 1);
+    })
+    .join()
+    .unwrap();
+    assert_eq!(pool.in_use(), 0);
+    assert_eq!(pool.available_noalloc(), 2);
+}
+
+#[test]
+fn cached_pool_retires_worn_out_items() {
+    use concurrent_pool::{Builder, CachedPool};
+
+    let pool = Builder::<u32>::new()
+        .capacity(1)
+        .max_uses(2)
+        .build_shared()
+        .into_arc();
+    let cached = CachedPool::new(pool.clone(), 1);
+    drop(cached.pull().unwrap());
+    assert_eq!(pool.in_use(), 1);
+    drop(cached.pull().unwrap());
+    assert_eq!(pool.allocated(), 0);
+    drop(cached.pull().unwrap());
+    assert_eq!(pool.allocated(), 1);
+}
+
+#[test]
+fn shrink_to_prealloc_frees_idle_items() {
+    let pool = Pool::<BigStruct>::new(2, 6);
+    let items: Vec<_> = (0..5).map(|_| pool.pull().unwrap()).collect();
+    drop(items);
+    assert_eq!(pool.allocated(), 5);
+    let item = pool.pull().unwrap();
+    assert_eq!(pool.shrink_to_prealloc(), 3);
+    assert_eq!(pool.allocated(), 2);
+    assert_eq!(pool.available_noalloc(), 1);
+    drop(item);
+}
+
+#[test]
+fn reclaim_frees_least_recently_used_items() {
+    let pool = Pool::<usize>::new(0, 3);
+    let mut items: Vec<_> = (0..3).map(|_| pool.pull().unwrap()).collect();
+    for (i, item) in items.iter_mut().enumerate() {
+        **item = i + 1;
+    }
+    // Recycle in the order 2, 1, 3.
+    let last = items.pop().unwrap();
+    items.swap(0, 1);
+    drop(items);
+    drop(last);
+    assert_eq!(pool.reclaim_now(1), 1);
+    assert_eq!(*pool.pull().unwrap(), 1);
+    assert_eq!(*pool.pull().unwrap(), 3);
+}
+
+#[test]
+#[cfg(feature = "instrument")]
+fn outstanding_reports_pull_call_sites() {
+    let pool = Arc::new(Pool::<u32>::with_capacity(8));
+    let owned = pool.pull_owned().unwrap();
+    let owned_line = line!() - 1;
+    let items = pool.pull_n(2).unwrap();
+    let n_line = line!() - 1;
+    let detached = pool.pull().unwrap().detach();
+    drop(pool.pull_unique().unwrap());
+
+    let outstanding = pool.outstanding();
+    assert_eq!(outstanding.len(), 3);
+    assert!(outstanding.iter().all(|r| r.location().file() == file!()));
+    let mut lines: Vec<_> = outstanding.iter().map(|r| r.location().line()).collect();
+    lines.sort();
+    assert_eq!(lines, [owned_line, n_line, n_line]);
+    drop((owned, items, detached));
+    assert!(pool.outstanding().is_empty());
+}
+
+#[test]
+fn expired_lease_is_revoked_by_maintenance() {
+    use concurrent_pool::Builder;
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+    static REVOKED: AtomicUsize = AtomicUsize::new(0);
+    let pool = Builder::<u32>::new()
+        .capacity(2)
+        .on_lease_revoked(|_, _| {
+            REVOKED.fetch_add(1, Relaxed);
+        })
+        .build();
+    let returned = pool.pull_leased(Duration::from_millis(5)).unwrap();
+    let mut hung = pool.pull_leased(Duration::from_millis(5)).unwrap();
+    drop(returned);
+    *hung = 1;
+    std::thread::sleep(Duration::from_millis(10));
+    pool.maintain();
+    assert_eq!(REVOKED.load(Relaxed), 1);
+    assert!(hung.is_revoked());
+    assert_eq!(*hung, 1);
+    assert_eq!(pool.in_use(), 0);
+    let items = pool.pull_n(2).unwrap();
+    assert!(items.iter().all(|item| **item == 0));
+    drop(hung);
+    drop(items);
+    assert_eq!(pool.allocated(), 2);
+}
+
+#[test]
+fn expired_lease_is_revoked_for_blocked_pulls() {
+    use concurrent_pool::{Builder, ExhaustedPolicy};
+
+    let pool = Builder::<u32>::new()
+        .capacity(1)
+        .on_exhausted(ExhaustedPolicy::Block)
+        .build();
+    let hung = pool.pull_leased(Duration::from_millis(20)).unwrap();
+    assert!(pool.pull().is_ok());
+    assert!(hung.is_revoked());
+
+    let hung = pool.pull_leased(Duration::from_millis(20)).unwrap();
+    assert!(pool.pull_timeout(Duration::from_secs(10)).is_ok());
+    assert!(hung.is_revoked());
+}
+
+#[test]
+fn expired_lease_is_revoked_by_bulk_pulls() {
+    let pool = Pool::<u32>::with_capacity(2);
+    let _hung = pool.pull_leased(Duration::from_millis(5)).unwrap();
+    let _kept = pool.pull().unwrap();
+    std::thread::sleep(Duration::from_millis(10));
+    let items = pool.pull_n(1).unwrap();
+    assert_eq!(items.len(), 1);
+    drop(items);
+    let _hung = pool.pull_leased(Duration::from_millis(5)).unwrap();
+    std::thread::sleep(Duration::from_millis(10));
+    assert_eq!(pool.pull_up_to(2).len(), 1);
+}
+
+#[test]
+fn drain_idle_returns_values_and_frees_slots() {
+    use concurrent_pool::{Builder, PreallocMode};
+
+    let pool = Builder::<u32>::new()
+        .capacity(4)
+        .prealloc(3)
+        .prealloc_mode(PreallocMode::Deferred)
+        .build();
+    let mut item = pool.pull().unwrap();
+    *item = 7;
+    drop(item);
+    // One initialized idle item, and two deferred slots.
+    assert_eq!(pool.drain_idle(), vec![7]);
+    assert_eq!(pool.allocated(), 0);
+    assert_eq!(pool.available(), 4);
+    assert!(pool.pull().is_ok());
+}
+
+#[test]
+fn retain_idle_keeps_matching_items() {
+    let pool = Pool::<u32>::with_capacity(4);
+    let mut items: Vec<_> = (0..4).map(|_| pool.pull().unwrap()).collect();
+    for (i, item) in items.iter_mut().enumerate() {
+        **item = i as u32;
+    }
+    drop(items);
+    assert_eq!(pool.retain_idle(|n| n % 2 == 0), 2);
+    assert_eq!(pool.allocated(), 2);
+    assert_eq!(*pool.pull().unwrap(), 0);
+    assert_eq!(*pool.pull().unwrap(), 2);
+}
+
+#[test]
+fn reset_preallocates_again() {
+    use concurrent_pool::{Builder, PreallocMode};
+
+    let pool = Builder::<u32>::new()
+        .capacity(4)
+        .prealloc(2)
+        .prealloc_mode(PreallocMode::Deferred)
+        .build();
+    let mut items = pool.pull_n(4).unwrap();
+    *items[0] = 7;
+    drop(items);
+    assert_eq!(pool.allocated(), 4);
+    pool.clear();
+    assert_eq!(pool.allocated(), 0);
+    pool.reset();
+    assert_eq!(pool.allocated(), 2);
+    assert!(pool.pull_n(4).unwrap().iter().all(|item| **item == 0));
+}
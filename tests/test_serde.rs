@@ -0,0 +1,96 @@
+#![cfg(feature = "serde")]
+
+use std::sync::Arc;
+
+use concurrent_pool::Pool;
+
+#[test]
+fn serialize_entries_as_inner_item() {
+    let pool = Arc::new(Pool::<Vec<u32>>::with_capacity(3));
+    let item = pool.pull_with(|v| v.extend([1, 2])).unwrap();
+    assert_eq!(serde_json::to_string(&item).unwrap(), "[1,2]");
+    let owned = pool.pull_owned_with(|v| v.push(3)).unwrap();
+    assert_eq!(serde_json::to_string(&owned).unwrap(), "[3]");
+    let mut unique = pool.pull_unique().unwrap();
+    unique.push(4);
+    assert_eq!(serde_json::to_string(&unique).unwrap(), "[4]");
+    let first = owned.map_owned(|v| &v[0]);
+    assert_eq!(serde_json::to_string(&first).unwrap(), "3");
+}
+
+#[test]
+fn deserialize_reuses_pooled_allocation() {
+    let pool = Pool::<Vec<u32>>::with_capacity(1);
+    let ptr = {
+        let mut item = pool.pull().unwrap();
+        item.reserve(16);
+        item.as_ptr()
+    };
+    let mut de = serde_json::Deserializer::from_str("[4, 5]");
+    let item = pool.pull_deserialize(&mut de).unwrap();
+    assert_eq!(*item, vec![4, 5]);
+    assert_eq!(item.as_ptr(), ptr);
+
+    let mut de = serde_json::Deserializer::from_str("[6]");
+    assert!(pool.pull_deserialize(&mut de).is_err());
+}
+
+#[test]
+fn deserialize_seed_for_nested_values() {
+    use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+
+    struct Batch<'a>(&'a Pool<String>);
+
+    impl<'a, 'de> DeserializeSeed<'de> for Batch<'a> {
+        type Value = Vec<concurrent_pool::Entry<'a, String>>;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'a, 'de> Visitor<'de> for Batch<'a> {
+        type Value = Vec<concurrent_pool::Entry<'a, String>>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a sequence of strings")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut items = vec![];
+            while let Some(item) = seq.next_element_seed(self.0)? {
+                items.push(item);
+            }
+            Ok(items)
+        }
+    }
+
+    let pool = Pool::<String>::with_capacity(2);
+    let mut de = serde_json::Deserializer::from_str(r#"["a", "b"]"#);
+    let items = Batch(&pool).deserialize(&mut de).unwrap();
+    assert_eq!(
+        items.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
+        ["a", "b"]
+    );
+    assert_eq!(pool.in_use(), 2);
+}
+
+#[test]
+fn deserialize_config_fields() {
+    use concurrent_pool::{ClearPolicy, Config};
+
+    let config: Config<u32> =
+        serde_json::from_str(r#"{ "name": "ids", "prealloc": 1, "clear_policy": "on_pull" }"#)
+            .unwrap();
+    assert_eq!(config.name.as_deref(), Some("ids"));
+    assert_eq!(config.capacity, Config::<u32>::default().capacity);
+    assert_eq!(config.prealloc, 1);
+    assert_eq!(config.clear_policy, ClearPolicy::OnPull);
+    assert!(serde_json::from_str::<Config<u32>>(r#"{ "clear_func": 1 }"#).is_err());
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "stable_deref")]
+
+use std::sync::Arc;
+
+use concurrent_pool::Pool;
+use stable_deref_trait::{CloneStableDeref, StableDeref};
+
+fn address<P: StableDeref>(ptr: &P) -> *const P::Target {
+    &**ptr
+}
+
+#[test]
+fn entries_deref_to_stable_address() {
+    let pool = Arc::new(Pool::<String>::with_capacity(2));
+    let item = pool.pull_owned_with(|s| s.push_str("stable")).unwrap();
+    let before = address(&item);
+    let moved = Box::new(item);
+    assert_eq!(address(&*moved), before);
+
+    fn clone_of<P: CloneStableDeref>(ptr: &P) -> P {
+        ptr.clone()
+    }
+    let clone = clone_of(&*moved);
+    assert_eq!(address(&clone), before);
+}
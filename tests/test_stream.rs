@@ -0,0 +1,58 @@
+#![cfg(feature = "stream")]
+
+use std::io;
+use std::sync::{Arc, mpsc};
+
+use concurrent_pool::{Builder, Pool, PullError};
+use futures::StreamExt;
+use futures::executor::block_on;
+
+#[test]
+fn stream_yields_available_items() {
+    let pool = Arc::new(Pool::<usize>::with_capacity(3));
+    let items: Vec<_> = block_on(pool.entries_stream().take(3).collect());
+    assert_eq!(items.len(), 3);
+    assert!(items.iter().all(Result::is_ok));
+    assert_eq!(pool.available(), 0);
+}
+
+#[test]
+fn stream_wakes_up_when_item_recycled() {
+    let pool = Arc::new(Pool::<usize>::with_capacity(2));
+    let (tx, rx) = mpsc::channel();
+    let consumer_pool = pool.clone();
+    let consumer = std::thread::spawn(move || {
+        block_on(async {
+            let mut stream = consumer_pool.entries_stream();
+            for i in 0..100 {
+                let mut item = stream.next().await.unwrap().unwrap();
+                *item.get_mut().unwrap() = i;
+                tx.send(item).unwrap();
+            }
+        })
+    });
+
+    let mut counter = 0;
+    while let Ok(item) = rx.recv() {
+        assert_eq!(*item, counter);
+        counter += 1;
+    }
+    consumer.join().unwrap();
+    assert_eq!(counter, 100);
+    assert_eq!(pool.available(), 2);
+}
+
+#[test]
+fn stream_yields_errors_and_ends_when_closed() {
+    let pool = Builder::<usize>::new()
+        .capacity(1)
+        .try_factory(|| Err(io::Error::other("unavailable")))
+        .build_shared();
+    let mut stream = pool.entries_stream();
+    assert!(matches!(
+        block_on(stream.next()),
+        Some(Err(PullError::Factory(_)))
+    ));
+    pool.close();
+    assert!(block_on(stream.next()).is_none());
+}